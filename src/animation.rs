@@ -1,6 +1,12 @@
 use core::marker::PhantomData;
 
-use super::NUM_DIGITS;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::format::{self, Alignment, IntFormat};
+use super::glyph::{Glyph, Segments};
+use super::interface::Interface;
+use super::{glyphs, Error, FontTable, Lights, HCS12SS59T, NUM_DIGITS};
 
 pub mod mode {
     pub trait Mode {}
@@ -13,73 +19,2155 @@ use mode::*;
 
 /// Text that has a window scrolling over it
 ///
-/// [ScrollingText::get_next()] returns an iterator which is a moving window on the text. It yields 12 characters and is moved by one character every time the function is called.
-pub struct ScrollingText<'a, MODE> {
+/// [ScrollingText::get_next()] returns an iterator which is a moving window on the text. It yields `N` (default 12) characters and is moved by one character every time the function is called.
+///
+/// Positions are counted in characters, so multi-byte UTF-8 text is safe (characters missing in the font are shown as `?`).
+/// Use [ScrollingText::builder()] for more options.
+pub struct ScrollingText<'a, MODE, const N: usize = NUM_DIGITS> {
     content: &'a str,
+    separator: &'a str,
+    gap: usize,
+    step: usize,
+    pad: char,
+    short_text: ShortText,
+    direction: Direction,
+    dwell: usize,
+    dwelled: usize,
     idx: usize,
     reverse: bool,
-    always: bool,
     _mode: PhantomData<MODE>,
 }
 
+/// Handling of text which fits on the display
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShortText {
+    /// Show the text without moving it
+    #[default]
+    Static,
+    /// Scroll the text anyway, [Cycle] repeats it and [LeftRight] moves it between the display edges
+    Scroll,
+}
+
+/// Direction the window moves over the text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Window moves to the end of the text, the text moves to the left
+    #[default]
+    Forward,
+    /// Window moves to the start of the text, the text moves to the right
+    ///
+    /// [LeftRight] starts at the end of the text.
+    Reverse,
+}
+
 impl<'a, M: Mode> ScrollingText<'a, M> {
     /// Crate a new ScrollingText with mode [Mode]
     ///
     /// `short_text_scrolling` sets wether text shorter than the display will scroll.
-    #[allow(unused_variables)]
     pub fn new(data: &'a str, short_text_scrolling: bool, mode: M) -> ScrollingText<'a, M> {
+        Self::new_with_digits(data, short_text_scrolling, mode)
+    }
+}
+
+impl<'a> ScrollingText<'a, Cycle> {
+    /// Returns a [ScrollBuilder] to configure a scrolling text
+    pub fn builder(text: &'a str) -> ScrollBuilder<'a, Cycle> {
+        ScrollBuilder {
+            text: ScrollingText::new_with_digits(text, false, Cycle),
+        }
+    }
+}
+
+impl<'a, M: Mode, const N: usize> ScrollingText<'a, M, N> {
+    /// Crate a new ScrollingText for a display with `N` digits
+    ///
+    /// Same as [new()](ScrollingText::new()), the window is `N` characters wide.
+    #[allow(unused_variables)]
+    pub fn new_with_digits(data: &'a str, short_text_scrolling: bool, mode: M) -> Self {
         ScrollingText {
             content: data,
+            separator: "",
+            gap: 0,
+            step: 1,
+            pad: ' ',
+            short_text: if short_text_scrolling {
+                ShortText::Scroll
+            } else {
+                ShortText::Static
+            },
+            direction: Direction::Forward,
+            dwell: 0,
+            dwelled: 0,
+            idx: 0,
+            reverse: false,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Replace the text, keeping all settings
+    ///
+    /// The window stays at its position, clamped to the length of the new text.
+    pub fn set_text(&mut self, text: &'a str) {
+        self.content = text;
+        self.idx = self.idx.min(text.chars().count());
+        self.dwelled = 0;
+    }
+
+    /// Move the window back to the start of the text
+    pub fn reset(&mut self) {
+        self.idx = 0;
+        self.reverse = false;
+        self.dwelled = 0;
+    }
+
+    /// Set the direction the window moves
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    fn into_mode<T>(self) -> ScrollingText<'a, T, N> {
+        self.into_digits()
+    }
+
+    fn into_digits<T, const D: usize>(self) -> ScrollingText<'a, T, D> {
+        ScrollingText {
+            content: self.content,
+            separator: self.separator,
+            gap: self.gap,
+            step: self.step,
+            pad: self.pad,
+            short_text: self.short_text,
+            direction: self.direction,
+            dwell: self.dwell,
+            dwelled: 0,
             idx: 0,
             reverse: false,
-            always: short_text_scrolling,
             _mode: PhantomData,
         }
     }
 }
 
-impl ScrollingText<'_, Cycle> {
+impl<'a, const N: usize> ScrollingText<'a, Cycle, N> {
+    /// Insert `separator` between the end of the text and its wrapped beginning
+    pub fn with_separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Insert `gap` blanks between the end of the text (and separator) and its wrapped beginning
+    pub fn with_gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
     /// Get cycling text
     ///
-    /// The window wraps to the start of the text if the end is reached.
+    /// The window wraps to the start of the text if the end is reached,
+    /// with the [separator](Self::with_separator()) and [gap](Self::with_gap()) in between.
     ///
     /// Text shorter than the display will be repeated.
-    pub fn get_next(&mut self) -> core::iter::Skip<core::iter::Cycle<core::str::Chars>> {
-        if self.content.len() <= NUM_DIGITS && !self.always {
-            return self.content.chars().cycle().skip(0);
-        }
-        let disp_iter = self.content.chars().cycle().skip(self.idx);
-        self.idx += 1 % NUM_DIGITS;
-
-        disp_iter
+    pub fn get_next(&mut self) -> impl Iterator<Item = char> + '_ {
+        let len = self.content.chars().count();
+        let skip = if len <= N && self.short_text == ShortText::Static {
+            0
+        } else {
+            let period = len + self.separator.chars().count() + self.gap;
+            let period = period.max(1);
+            let idx = self.idx;
+            self.idx = match self.direction {
+                Direction::Forward => (self.idx + self.step) % period,
+                Direction::Reverse => (self.idx + period - self.step % period) % period,
+            };
+            idx
+        };
+        self.content
+            .chars()
+            .chain(self.separator.chars())
+            .chain(core::iter::repeat_n(self.pad, self.gap))
+            .cycle()
+            .skip(skip)
     }
 }
-impl ScrollingText<'_, LeftRight> {
+impl<const N: usize> ScrollingText<'_, LeftRight, N> {
+    /// Repeat the frames at both ends `dwell` times before changing the direction
+    pub fn with_dwell(mut self, dwell: usize) -> Self {
+        self.dwell = dwell;
+        self
+    }
+
     /// Get a scrolling window which changes direction when reaching the text bounds
     ///
-    /// _Note:_ Currently scrolling on text shorter than the display isn't implemented.
-    /// Text will be static if shorter or equal.
-    pub fn get_next(&mut self) -> core::str::Chars {
-        if self.content.len() <= NUM_DIGITS {
-            return self.content.chars(); // If content fits on display no scrolling is necessary
+    /// Text shorter than the display is static, unless [ShortText::Scroll] is set.
+    /// Then the text moves between the left and right edge of the display.
+    pub fn get_next(&mut self) -> impl Iterator<Item = char> + '_ {
+        let len = self.content.chars().count();
+        let (offset, skip) = if len > N {
+            (0, self.position(len - N))
+        } else if self.short_text == ShortText::Scroll {
+            (self.position(N - len), 0)
+        } else {
+            // If content fits on display no scrolling is necessary
+            (0, 0)
+        };
+        core::iter::repeat_n(self.pad, offset)
+            .chain(self.content.chars().skip(skip))
+            .take(N)
+    }
+
+    /// Returns the current position from the start of the text, taking the direction into account
+    fn position(&mut self, max: usize) -> usize {
+        let pos = self.step(max);
+        match self.direction {
+            Direction::Forward => pos,
+            Direction::Reverse => max - pos,
         }
+    }
 
-        let current = self.content[self.idx..self.idx + NUM_DIGITS].chars();
+    /// Returns the current position and moves it by the step size, bouncing between `0` and `max`
+    fn step(&mut self, max: usize) -> usize {
+        let current = self.idx.min(max);
+
+        if (current == 0 || current >= max) && self.dwelled < self.dwell {
+            self.dwelled += 1;
+            return current;
+        }
+        self.dwelled = 0;
 
-        if self.idx + NUM_DIGITS >= self.content.len() {
+        if current >= max {
             self.reverse = true;
         }
 
-        if self.idx == 0 {
+        if current == 0 {
             self.reverse = false;
         }
 
         if !self.reverse {
-            self.idx += 1;
+            self.idx = (current + self.step).min(max);
         } else {
-            self.idx -= 1;
+            self.idx = current.saturating_sub(self.step);
         }
 
         current
     }
 }
+
+/// Builder for [ScrollingText]
+///
+/// Created with [ScrollingText::builder()], scrolls in [Cycle] mode on 12 digits by default.
+pub struct ScrollBuilder<'a, MODE, const N: usize = NUM_DIGITS> {
+    text: ScrollingText<'a, MODE, N>,
+}
+
+impl<'a, M: Mode, const N: usize> ScrollBuilder<'a, M, N> {
+    /// Wrap around to the start of the text at its end
+    pub fn cycle(self) -> ScrollBuilder<'a, Cycle, N> {
+        ScrollBuilder {
+            text: self.text.into_mode(),
+        }
+    }
+
+    /// Change the direction at the ends of the text
+    pub fn bounce(self) -> ScrollBuilder<'a, LeftRight, N> {
+        ScrollBuilder {
+            text: self.text.into_mode(),
+        }
+    }
+
+    /// Set the number of digits of the display
+    pub fn digits<const D: usize>(self) -> ScrollBuilder<'a, M, D> {
+        ScrollBuilder {
+            text: self.text.into_digits(),
+        }
+    }
+
+    /// Move the window by `step` characters per frame (at least one)
+    pub fn step(mut self, step: usize) -> Self {
+        self.text.step = step.max(1);
+        self
+    }
+
+    /// Set the character for blanks, e.g. padding of short frames and gaps
+    pub fn pad(mut self, pad: char) -> Self {
+        self.text.pad = pad;
+        self
+    }
+
+    /// Set the direction the window moves
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.text.direction = direction;
+        self
+    }
+
+    /// Set the handling of text which fits on the display
+    pub fn short_text(mut self, short_text: ShortText) -> Self {
+        self.text.short_text = short_text;
+        self
+    }
+
+    /// Construct the scrolling text
+    pub fn build(self) -> ScrollingText<'a, M, N> {
+        self.text
+    }
+}
+
+impl<'a, const N: usize> ScrollBuilder<'a, Cycle, N> {
+    /// Insert `separator` between the end of the text and its wrapped beginning
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.text.separator = separator;
+        self
+    }
+
+    /// Insert `gap` blanks between the end of the text (and separator) and its wrapped beginning
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.text.gap = gap;
+        self
+    }
+}
+
+impl<const N: usize> ScrollBuilder<'_, LeftRight, N> {
+    /// Repeat the frames at both ends `dwell` times before changing the direction
+    pub fn dwell(mut self, dwell: usize) -> Self {
+        self.text.dwell = dwell;
+        self
+    }
+}
+
+/// Sequence of display frames
+///
+/// Implement it for own animations to play them with [run()] or [HCS12SS59T::run_animation()].
+pub trait Animation {
+    /// Characters of the next frame, starting at the leftmost digit
+    ///
+    /// Frames shorter than the display are padded with the fill character of the driver.
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable>;
+
+    /// Suggested time a frame is shown in milliseconds
+    fn frame_ms(&self) -> u32 {
+        300
+    }
+}
+
+impl<const N: usize> Animation for ScrollingText<'_, Cycle, N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next().map(FontTable::from)
+    }
+}
+
+impl<const N: usize> Animation for ScrollingText<'_, LeftRight, N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next().map(FontTable::from)
+    }
+}
+
+/// Collects the first `N` characters into a frame, padded with `pad`
+fn to_frame<const N: usize>(text: impl IntoIterator<Item = char>, pad: char) -> [FontTable; N] {
+    let mut frame = [pad.into(); N];
+    for (cell, c) in frame.iter_mut().zip(text) {
+        *cell = c.into();
+    }
+    frame
+}
+
+impl<const N: usize> ScrollingText<'_, Cycle, N> {
+    /// Like [get_next()](Self::get_next()), but returns the frame as fixed-size array
+    ///
+    /// Characters are mapped to the font and short frames padded with the [pad](ScrollBuilder::pad()) character,
+    /// the frame can be stored, compared or passed to [BufferedHCS12SS59T::display()](crate::framebuffer::BufferedHCS12SS59T::display()).
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        let pad = self.pad;
+        to_frame(self.get_next(), pad)
+    }
+}
+
+/// Endless sequence of frames, see [get_next_frame()](ScrollingText::get_next_frame())
+impl<const N: usize> Iterator for ScrollingText<'_, Cycle, N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.get_next_frame())
+    }
+}
+
+impl<const N: usize> ScrollingText<'_, LeftRight, N> {
+    /// Like [get_next()](Self::get_next()), but returns the frame as fixed-size array
+    ///
+    /// Characters are mapped to the font and short frames padded with the [pad](ScrollBuilder::pad()) character,
+    /// the frame can be stored, compared or passed to [BufferedHCS12SS59T::display()](crate::framebuffer::BufferedHCS12SS59T::display()).
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        let pad = self.pad;
+        to_frame(self.get_next(), pad)
+    }
+}
+
+/// Endless sequence of frames, see [get_next_frame()](ScrollingText::get_next_frame())
+impl<const N: usize> Iterator for ScrollingText<'_, LeftRight, N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.get_next_frame())
+    }
+}
+
+/// One-shot animation sliding text in from the right edge or out to the left edge
+///
+/// Unlike [ScrollingText] it ends in a static display: the [Iterator] ends after the last frame,
+/// the [Animation] keeps repeating it. Only the first `N` (default 12) characters of the text are shown.
+pub struct Slide<'a, const N: usize = NUM_DIGITS> {
+    text: &'a str,
+    pad: char,
+    lead: usize,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Slide<'a> {
+    /// Slide `text` in from the right edge until it starts at the leftmost digit
+    ///
+    /// The first frame shows the first character on the rightmost digit.
+    pub fn scroll_in(text: &'a str) -> Self {
+        Self::scroll_in_with_digits(text)
+    }
+
+    /// Slide `text`, which is currently shown starting at the leftmost digit, out to the left edge
+    ///
+    /// The first frame is already moved by one digit, the last one is blank.
+    pub fn scroll_out(text: &'a str) -> Self {
+        Self::scroll_out_with_digits(text)
+    }
+}
+
+impl<'a, const N: usize> Slide<'a, N> {
+    /// Same as [scroll_in()](Slide::scroll_in()) for a display with `N` digits
+    pub fn scroll_in_with_digits(text: &'a str) -> Self {
+        let lead = N.saturating_sub(1);
+        Slide {
+            text,
+            pad: ' ',
+            lead,
+            pos: 0,
+            end: lead,
+        }
+    }
+
+    /// Same as [scroll_out()](Slide::scroll_out()) for a display with `N` digits
+    pub fn scroll_out_with_digits(text: &'a str) -> Self {
+        Slide {
+            text,
+            pad: ' ',
+            lead: 0,
+            pos: 1,
+            end: text.chars().count().min(N),
+        }
+    }
+
+    /// Set the character for blanks
+    pub fn with_pad(mut self, pad: char) -> Self {
+        self.pad = pad;
+        self
+    }
+
+    /// Returns wether the last frame was returned
+    pub fn is_done(&self) -> bool {
+        self.pos > self.end
+    }
+
+    /// Returns the next frame, the last frame is repeated once the slide is done
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        let pos = self.pos.min(self.end);
+        self.pos = (self.pos + 1).min(self.end + 1);
+        let text = core::iter::repeat_n(self.pad, self.lead.saturating_sub(pos)).chain(
+            self.text
+                .chars()
+                .take(N)
+                .skip(pos.saturating_sub(self.lead)),
+        );
+        to_frame(text, self.pad)
+    }
+}
+
+/// Frames of the slide, ends after the last one
+impl<const N: usize> Iterator for Slide<'_, N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done() {
+            return None;
+        }
+        Some(self.get_next_frame())
+    }
+}
+
+impl<const N: usize> Animation for Slide<'_, N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    fn frame_ms(&self) -> u32 {
+        100
+    }
+}
+
+/// Effect of a [Transition]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Effect {
+    /// Digits change from left to right
+    #[default]
+    Wipe,
+    /// Digits change one by one in random order, the seed selects the order
+    Dissolve(u32),
+    /// Every digit rolls through the font until it reaches the new character
+    ///
+    /// Custom characters are not part of the roll and change in the first frame.
+    Roll,
+}
+
+/// One-shot animation changing the display from one text to another
+///
+/// Like [Slide] the [Iterator] ends after the last frame, which shows the new text, and the [Animation] keeps repeating it.
+pub struct Transition<const N: usize = NUM_DIGITS> {
+    from: [FontTable; N],
+    to: [FontTable; N],
+    effect: Effect,
+    rank: [usize; N],
+    shown: usize,
+    frames: usize,
+}
+
+impl Transition {
+    /// Change from the text `from` to `to`, both starting at the leftmost digit
+    pub fn new(from: &str, to: &str, effect: Effect) -> Self {
+        Self::new_with_digits(from, to, effect)
+    }
+}
+
+/// Number of characters in the built-in font, which starts at [FontTable::ChatAt]
+const FONT_LEN: u8 = 64;
+
+impl<const N: usize> Transition<N> {
+    /// Same as [new()](Transition::new()) for a display with `N` digits
+    pub fn new_with_digits(from: &str, to: &str, effect: Effect) -> Self {
+        Self::from_frames(
+            to_frame(from.chars(), ' '),
+            to_frame(to.chars(), ' '),
+            effect,
+        )
+    }
+
+    /// Change between two frames, e.g. from [get_next_frame()](ScrollingText::get_next_frame())
+    pub fn from_frames(from: [FontTable; N], to: [FontTable; N], effect: Effect) -> Self {
+        let mut rank = core::array::from_fn(|i| i);
+        let frames = match effect {
+            Effect::Wipe => N,
+            Effect::Dissolve(seed) => {
+                // Fisher-Yates shuffle with xorshift32, which must not start at zero
+                let mut state = seed.max(1);
+                for i in (1..N).rev() {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    rank.swap(i, state as usize % (i + 1));
+                }
+                N
+            }
+            Effect::Roll => (0..N)
+                .map(|i| Self::roll_distance(from[i], to[i]))
+                .max()
+                .unwrap_or(0),
+        };
+        Transition {
+            from,
+            to,
+            effect,
+            rank,
+            shown: 0,
+            frames,
+        }
+    }
+
+    /// Frames until a digit rolled from `from` to `to`, custom characters change immediately
+    fn roll_distance(from: FontTable, to: FontTable) -> usize {
+        let (from, to) = (from as u8, to as u8);
+        if from == to {
+            0
+        } else if from < FontTable::ChatAt as u8 || to < FontTable::ChatAt as u8 {
+            1
+        } else {
+            ((to + FONT_LEN - from) % FONT_LEN) as usize
+        }
+    }
+
+    /// Returns wether the last frame was returned
+    pub fn is_done(&self) -> bool {
+        self.shown >= self.frames
+    }
+
+    /// Returns the next frame, the last frame is repeated once the transition is done
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        self.shown = (self.shown + 1).min(self.frames);
+        let k = self.shown;
+        core::array::from_fn(|i| {
+            let (from, to) = (self.from[i], self.to[i]);
+            let changed = match self.effect {
+                Effect::Wipe => i < k,
+                Effect::Dissolve(_) => self.rank[i] < k,
+                Effect::Roll => {
+                    let distance = Self::roll_distance(from, to);
+                    if k < distance {
+                        let base = FontTable::ChatAt as u8;
+                        let code = (from as u8 - base + k as u8) % FONT_LEN + base;
+                        return FontTable::try_from(code).unwrap_or(to);
+                    }
+                    true
+                }
+            };
+            if changed {
+                to
+            } else {
+                from
+            }
+        })
+    }
+}
+
+/// Frames of the transition, ends after the last one
+impl<const N: usize> Iterator for Transition<N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done() {
+            return None;
+        }
+        Some(self.get_next_frame())
+    }
+}
+
+impl<const N: usize> Animation for Transition<N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    fn frame_ms(&self) -> u32 {
+        match self.effect {
+            Effect::Roll => 30,
+            _ => 80,
+        }
+    }
+}
+
+/// Frame of a [FrameAnimation]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frame<'a> {
+    /// Text starting at the leftmost digit, short text is padded with blanks
+    pub text: &'a str,
+    /// Time the frame is shown in milliseconds
+    pub duration_ms: u32,
+}
+
+impl<'a> Frame<'a> {
+    /// Show `text` for `duration_ms`
+    pub const fn new(text: &'a str, duration_ms: u32) -> Self {
+        Self { text, duration_ms }
+    }
+}
+
+/// Playback order of a [FrameAnimation]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Repeat {
+    /// Play the frames once and keep showing the last one
+    Once,
+    /// Start again with the first frame after the last one
+    #[default]
+    Loop,
+    /// Play the frames forwards and backwards, the first and last frame are not repeated
+    PingPong,
+}
+
+/// Animation playing a sequence of pre-authored frames, e.g. a logo or an intro
+///
+/// ``` text
+/// const INTRO: &[Frame] = &[Frame::new("     HI     ", 500), Frame::new("    HELLO   ", 1000)];
+/// let mut intro = FrameAnimation::new(INTRO).with_repeat(Repeat::Once);
+/// ```
+///
+/// Every frame has its own duration, [tick()](Self::tick()) returns the frames when they are due.
+/// [run()] without a fixed `frame_ms` shows every frame for its duration as well.
+pub struct FrameAnimation<'a, const N: usize = NUM_DIGITS> {
+    frames: &'a [Frame<'a>],
+    repeat: Repeat,
+    next: usize,
+    forward: bool,
+    current: Option<usize>,
+    done: bool,
+    shown_at: Option<u32>,
+}
+
+impl<'a> FrameAnimation<'a> {
+    /// Play `frames` in a [loop](Repeat::Loop)
+    pub fn new(frames: &'a [Frame<'a>]) -> Self {
+        Self::new_with_digits(frames)
+    }
+}
+
+impl<'a, const N: usize> FrameAnimation<'a, N> {
+    /// Same as [new()](FrameAnimation::new()) for a display with `N` digits
+    pub fn new_with_digits(frames: &'a [Frame<'a>]) -> Self {
+        FrameAnimation {
+            frames,
+            repeat: Repeat::Loop,
+            next: 0,
+            forward: true,
+            current: None,
+            done: frames.is_empty(),
+            shown_at: None,
+        }
+    }
+
+    /// Set the playback order
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Start again with the first frame
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.forward = true;
+        self.current = None;
+        self.done = self.frames.is_empty();
+        self.shown_at = None;
+    }
+
+    /// Returns wether the last frame was returned, never true when repeating
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Returns the next frame, once [done](Self::is_done()) the last frame is repeated
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        if !self.done {
+            self.advance();
+        }
+        match self.current {
+            Some(idx) => to_frame(self.frames[idx].text.chars(), ' '),
+            None => [FontTable::CharSpace; N],
+        }
+    }
+
+    /// Returns the next frame if the current one was shown for its duration at `now_ms`
+    ///
+    /// The first call always returns the first frame.
+    pub fn tick(&mut self, now_ms: u32) -> Option<[FontTable; N]> {
+        if let Some(shown_at) = self.shown_at {
+            if self.done || now_ms.wrapping_sub(shown_at) < self.frame_ms() {
+                return None;
+            }
+        }
+        self.shown_at = Some(now_ms);
+        Some(self.get_next_frame())
+    }
+
+    fn advance(&mut self) {
+        let idx = self.next;
+        let last = self.frames.len() - 1;
+        self.current = Some(idx);
+        self.next = match self.repeat {
+            Repeat::Once => {
+                self.done = idx == last;
+                idx + 1
+            }
+            Repeat::Loop => (idx + 1) % self.frames.len(),
+            Repeat::PingPong if last == 0 => 0,
+            Repeat::PingPong => {
+                if idx == last {
+                    self.forward = false;
+                } else if idx == 0 {
+                    self.forward = true;
+                }
+                if self.forward {
+                    idx + 1
+                } else {
+                    idx - 1
+                }
+            }
+        };
+    }
+}
+
+/// Frames of the animation, ends after the last one with [Repeat::Once]
+impl<const N: usize> Iterator for FrameAnimation<'_, N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        Some(self.get_next_frame())
+    }
+}
+
+impl<const N: usize> Animation for FrameAnimation<'_, N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    /// Duration of the current frame, or of the first one before it is shown
+    fn frame_ms(&self) -> u32 {
+        self.frames
+            .get(self.current.unwrap_or(0))
+            .map_or(0, |frame| frame.duration_ms)
+    }
+}
+
+/// Where a [Pager] breaks the text into pages
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Wrap {
+    /// Fill every page completely, words can be cut anywhere
+    #[default]
+    Chars,
+    /// Break at spaces, the rest of the page is padded. Words longer than the display are cut.
+    Words,
+    /// Like [Wrap::Words], but words longer than the display are continued on the next page after a `-`
+    Hyphenate,
+}
+
+/// First line of `text` for a display with `width` digits
+///
+/// Returns the line, whether it needs a hyphen and the remaining text.
+fn wrap_line(text: &str, width: usize, hyphenate: bool) -> (&str, bool, &str) {
+    let text = text.trim_start_matches(' ');
+    let Some((cut, next)) = text.char_indices().nth(width) else {
+        return (text, false, "");
+    };
+    let space = if next == ' ' {
+        Some(cut)
+    } else {
+        text[..cut].rfind(' ')
+    };
+    if let Some(space) = space {
+        return (&text[..space], false, &text[space..]);
+    }
+    if hyphenate && width > 1 {
+        // The text has more than `width` characters
+        let (cut, _) = text.char_indices().nth(width - 1).unwrap();
+        return (&text[..cut], true, &text[cut..]);
+    }
+    (&text[..cut], false, &text[cut..])
+}
+
+/// Long text split into pages of `N` characters, e.g. for menus or logs
+///
+/// Pages are flipped with [next_page()](Self::next_page()) and [prev_page()](Self::prev_page()), or automatically
+/// when played as an animation with the dwell time of [auto_advance()](Self::auto_advance()).
+/// By default pages are filled completely, use [with_wrap()](Self::with_wrap()) to keep words together.
+pub struct Pager<'a, const N: usize = NUM_DIGITS> {
+    text: &'a str,
+    wrap: Wrap,
+    pages: usize,
+    page: usize,
+    dwell_ms: u32,
+    started: bool,
+    shown_at: Option<u32>,
+}
+
+impl<'a> Pager<'a> {
+    /// Split `text` into pages of 12 characters
+    pub fn new(text: &'a str) -> Self {
+        Self::new_with_digits(text)
+    }
+}
+
+impl<'a, const N: usize> Pager<'a, N> {
+    /// Same as [new()](Pager::new()) for a display with `N` digits
+    pub fn new_with_digits(text: &'a str) -> Self {
+        Pager {
+            text,
+            wrap: Wrap::Chars,
+            pages: text.chars().count().div_ceil(N.max(1)).max(1),
+            page: 0,
+            dwell_ms: 2000,
+            started: false,
+            shown_at: None,
+        }
+    }
+
+    /// Set where the text is broken into pages, the pager starts again at the first page
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self.page = 0;
+        self.pages = match wrap {
+            Wrap::Chars => self.text.chars().count().div_ceil(N.max(1)).max(1),
+            _ => self.lines().count().max(1),
+        };
+        self
+    }
+
+    /// Lines of the text broken at words, with a flag for a trailing hyphen
+    fn lines(&self) -> impl Iterator<Item = (&'a str, bool)> {
+        let hyphenate = self.wrap == Wrap::Hyphenate;
+        let mut rest = self.text;
+        core::iter::from_fn(move || {
+            if rest.trim_start_matches(' ').is_empty() {
+                return None;
+            }
+            let (line, hyphen, next) = wrap_line(rest, N.max(1), hyphenate);
+            rest = next;
+            Some((line, hyphen))
+        })
+    }
+
+    /// Set the time a page is shown when played as animation, in milliseconds
+    pub fn auto_advance(mut self, dwell_ms: u32) -> Self {
+        self.dwell_ms = dwell_ms;
+        self
+    }
+
+    /// Number of pages, at least one
+    pub fn pages(&self) -> usize {
+        self.pages
+    }
+
+    /// Index of the current page
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Go to page `page`, clamped to the last page
+    pub fn set_page(&mut self, page: usize) {
+        self.page = page.min(self.pages - 1);
+    }
+
+    /// Flip to the next page, after the last page the first one follows
+    pub fn next_page(&mut self) {
+        self.page = (self.page + 1) % self.pages;
+    }
+
+    /// Flip to the previous page, before the first page the last one follows
+    pub fn prev_page(&mut self) {
+        self.page = (self.page + self.pages - 1) % self.pages;
+    }
+
+    /// Characters of the current page, the last page is padded with spaces
+    pub fn get_frame(&self) -> [FontTable; N] {
+        if self.wrap == Wrap::Chars {
+            return to_frame(self.text.chars().skip(self.page * N), ' ');
+        }
+        let (line, hyphen) = self.lines().nth(self.page).unwrap_or_default();
+        to_frame(line.chars().chain(hyphen.then_some('-')), ' ')
+    }
+
+    /// Returns the next page when the current one was shown for the dwell time at `now_ms`
+    ///
+    /// The first call always returns the current page.
+    pub fn tick(&mut self, now_ms: u32) -> Option<[FontTable; N]> {
+        if let Some(shown_at) = self.shown_at {
+            if now_ms.wrapping_sub(shown_at) < self.dwell_ms {
+                return None;
+            }
+        }
+        self.shown_at = Some(now_ms);
+        Some(self.flip())
+    }
+
+    fn flip(&mut self) -> [FontTable; N] {
+        if self.started {
+            self.next_page();
+        }
+        self.started = true;
+        self.get_frame()
+    }
+}
+
+/// Flips through the pages endlessly, starting with the current page
+impl<const N: usize> Animation for Pager<'_, N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.flip()
+    }
+
+    fn frame_ms(&self) -> u32 {
+        self.dwell_ms
+    }
+}
+
+/// Control code of a [MarkupText]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Markup {
+    Pause(u32),
+    Speed(u32),
+    Blink(bool),
+}
+
+/// Part of a [MarkupText]
+enum Token {
+    Char(char),
+    Code(Markup),
+}
+
+/// Splits text into characters and control codes, malformed codes are dropped
+fn tokens(text: &str) -> impl Iterator<Item = Token> + '_ {
+    let mut rest = text;
+    core::iter::from_fn(move || loop {
+        let mut chars = rest.chars();
+        let c = chars.next()?;
+        rest = chars.as_str();
+        if c != '{' {
+            return Some(Token::Char(c));
+        }
+        if let Some(after) = rest.strip_prefix('{') {
+            rest = after;
+            return Some(Token::Char('{'));
+        }
+        let Some((code, after)) = rest.split_once('}') else {
+            rest = "";
+            return None;
+        };
+        rest = after;
+        if let Some(code) = parse_markup(code) {
+            return Some(Token::Code(code));
+        }
+    })
+}
+
+fn parse_markup(code: &str) -> Option<Markup> {
+    let (name, arg) = code.trim().split_once(' ')?;
+    let arg = arg.trim();
+    match name {
+        "pause" => {
+            let (value, scale) = match arg.strip_suffix("ms") {
+                Some(value) => (value, 1),
+                None => match arg.strip_suffix('s') {
+                    Some(value) => (value, 1000),
+                    None => (arg, 1),
+                },
+            };
+            value
+                .parse::<u32>()
+                .ok()
+                .map(|value| Markup::Pause(value.saturating_mul(scale)))
+        }
+        "speed" => arg
+            .parse()
+            .ok()
+            .filter(|speed| *speed > 0)
+            .map(Markup::Speed),
+        "blink" => match arg {
+            "on" => Some(Markup::Blink(true)),
+            "off" => Some(Markup::Blink(false)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Scrolling text with inline control codes for the pacing
+///
+/// The text enters on the right and leaves on the left, then starts over. Codes take effect when the
+/// text in front of them has fully scrolled in:
+///
+/// - `{pause 1s}` or `{pause 500ms}` holds the frame for the given time
+/// - `{speed 2}` scrolls two times faster than the [base speed](Self::with_frame_ms())
+/// - `{blink on}` and `{blink off}` alternate the frames with blank ones
+///
+/// `{{` shows a `{`, unknown codes are ignored. Every pass starts with speed 1 and blinking off.
+///
+/// ``` text
+/// let mut news = MarkupText::new("BREAKING{pause 2s} {speed 2}{blink on}SALE{blink off} ALL DAY");
+/// loop {
+///     if let Some(frame) = news.tick(now_ms()) {
+///         vfd.display(frame)?;
+///     }
+/// }
+/// ```
+pub struct MarkupText<'a, const N: usize = NUM_DIGITS> {
+    text: &'a str,
+    len: usize,
+    pos: usize,
+    step_ms: u32,
+    speed: u32,
+    blink: bool,
+    blank_next: bool,
+    current_ms: u32,
+    shown_at: Option<u32>,
+}
+
+impl<'a> MarkupText<'a> {
+    /// Scroll `text` with one step every 300 ms
+    pub fn new(text: &'a str) -> Self {
+        Self::new_with_digits(text)
+    }
+}
+
+impl<'a, const N: usize> MarkupText<'a, N> {
+    /// Same as [new()](MarkupText::new()) for a display with `N` digits
+    pub fn new_with_digits(text: &'a str) -> Self {
+        let len = tokens(text)
+            .filter(|token| matches!(token, Token::Char(_)))
+            .count();
+        MarkupText {
+            text,
+            len,
+            pos: 0,
+            step_ms: 300,
+            speed: 1,
+            blink: false,
+            blank_next: false,
+            current_ms: 300,
+            shown_at: None,
+        }
+    }
+
+    /// Set the time of a step at speed 1 in milliseconds
+    pub fn with_frame_ms(mut self, frame_ms: u32) -> Self {
+        self.step_ms = frame_ms;
+        self.current_ms = frame_ms;
+        self
+    }
+
+    /// Returns the next frame, [frame_ms()](Animation::frame_ms()) is the time it should be shown
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        if self.blank_next {
+            self.blank_next = false;
+            self.current_ms = self.step_ms / self.speed;
+            return [FontTable::CharSpace; N];
+        }
+        if self.pos == 0 {
+            self.speed = 1;
+            self.blink = false;
+        }
+
+        let mut pause = 0;
+        let mut visible = 0;
+        let mut frame = [FontTable::CharSpace; N];
+        // The window starts `N` blanks in front of the text
+        let start = self.pos.saturating_sub(N);
+        let offset = N.saturating_sub(self.pos);
+        for token in tokens(self.text) {
+            match token {
+                Token::Char(c) => {
+                    if visible >= start && offset + visible - start < N {
+                        frame[offset + visible - start] = c.into();
+                    }
+                    visible += 1;
+                }
+                Token::Code(code) if visible == self.pos => match code {
+                    Markup::Pause(ms) => pause += ms,
+                    Markup::Speed(speed) => self.speed = speed,
+                    Markup::Blink(on) => self.blink = on,
+                },
+                Token::Code(_) => {}
+            }
+            if visible > self.pos {
+                break;
+            }
+        }
+
+        self.current_ms = self.step_ms / self.speed + pause;
+        self.blank_next = self.blink;
+        self.pos = (self.pos + 1) % (self.len + N);
+        frame
+    }
+
+    /// Returns the next frame if the current one was shown long enough at `now_ms`
+    ///
+    /// The first call always returns the first frame.
+    pub fn tick(&mut self, now_ms: u32) -> Option<[FontTable; N]> {
+        if let Some(shown_at) = self.shown_at {
+            if now_ms.wrapping_sub(shown_at) < self.current_ms {
+                return None;
+            }
+        }
+        self.shown_at = Some(now_ms);
+        Some(self.get_next_frame())
+    }
+}
+
+/// Scrolls the text endlessly
+impl<const N: usize> Animation for MarkupText<'_, N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    /// Duration of the frame returned last
+    fn frame_ms(&self) -> u32 {
+        self.current_ms
+    }
+}
+
+/// Knight-Rider style bar sweeping back and forth over the display
+///
+/// The bar is drawn with a custom character, load it with [load()](Self::load()) before playing the animation:
+///
+/// ``` text
+/// let mut scanner = Larson::new(FontTable::Ram0).with_width(3);
+/// scanner.load(&mut vfd)?;
+/// vfd.run_animation(&mut scanner, None, 100)?;
+/// ```
+pub struct Larson<const N: usize = NUM_DIGITS> {
+    slot: FontTable,
+    glyph: Glyph,
+    width: usize,
+    frame_ms: u32,
+    pos: usize,
+    forward: bool,
+}
+
+impl Larson {
+    /// Sweep a bar with a width of two digits, drawn with the CGRAM `slot`
+    pub fn new(slot: FontTable) -> Self {
+        Self::new_with_digits(slot)
+    }
+}
+
+impl<const N: usize> Larson<N> {
+    /// Same as [new()](Larson::new()) for a display with `N` digits
+    pub fn new_with_digits(slot: FontTable) -> Self {
+        Larson {
+            slot,
+            glyph: glyphs::BLOCK,
+            width: 2,
+            frame_ms: 80,
+            pos: 0,
+            forward: true,
+        }
+    }
+
+    /// Set the width of the bar in digits (at least one)
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// Set the time the bar stays on one position in milliseconds, see [Animation::frame_ms()]
+    pub fn with_frame_ms(mut self, frame_ms: u32) -> Self {
+        self.frame_ms = frame_ms;
+        self
+    }
+
+    /// Draw the bar with `glyph` instead of [all segments](glyphs::BLOCK)
+    pub fn with_glyph(mut self, glyph: Glyph) -> Self {
+        self.glyph = glyph;
+        self
+    }
+
+    /// Write the glyph of the bar to its CGRAM slot
+    pub fn load<IF, RstPin, VdonPin, Delay>(
+        &self,
+        vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        vfd.set_cgram_pattern(self.slot, self.glyph)
+    }
+
+    /// Returns the next frame, the bar moves by one digit
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        let pos = self.pos;
+        let last = N.saturating_sub(self.width);
+        if last > 0 {
+            if pos == last {
+                self.forward = false;
+            } else if pos == 0 {
+                self.forward = true;
+            }
+            self.pos = if self.forward { pos + 1 } else { pos - 1 };
+        }
+        core::array::from_fn(|i| {
+            if (pos..pos + self.width).contains(&i) {
+                self.slot
+            } else {
+                FontTable::CharSpace
+            }
+        })
+    }
+}
+
+impl<const N: usize> Animation for Larson<N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    fn frame_ms(&self) -> u32 {
+        self.frame_ms
+    }
+}
+
+/// One-shot animation of random characters settling into a text, e.g. for a boot screen
+///
+/// Every digit shows random characters of the built-in font until it settles on its character of the target text.
+/// The digits settle at random frames, the last one at [frames](Self::with_frames()).
+#[cfg(feature = "rand_core")]
+pub struct Matrix<R, const N: usize = NUM_DIGITS> {
+    target: [FontTable; N],
+    rng: R,
+    settle: [u32; N],
+    frames: u32,
+    shown: u32,
+}
+
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::RngCore> Matrix<R> {
+    /// Settle into `text` within 20 frames, drawing random numbers from `rng`
+    pub fn new(text: &str, rng: R) -> Self {
+        Self::new_with_digits(text, rng)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::RngCore, const N: usize> Matrix<R, N> {
+    /// Same as [new()](Matrix::new()) for a display with `N` digits
+    pub fn new_with_digits(text: &str, rng: R) -> Self {
+        let mut matrix = Matrix {
+            target: to_frame(text.chars(), ' '),
+            rng,
+            settle: [0; N],
+            frames: 0,
+            shown: 0,
+        };
+        matrix.reset_with_frames(20);
+        matrix
+    }
+
+    /// Set the number of frames until all digits settled (at least one)
+    pub fn with_frames(mut self, frames: u32) -> Self {
+        self.reset_with_frames(frames);
+        self
+    }
+
+    fn reset_with_frames(&mut self, frames: u32) {
+        self.frames = frames.max(1);
+        self.shown = 0;
+        for settle in self.settle.iter_mut() {
+            *settle = self.rng.next_u32() % self.frames + 1;
+        }
+        // The last digit settles with the last frame, so the animation doesn't end early
+        if let Some(last) = self.settle.iter_mut().max() {
+            *last = self.frames;
+        }
+    }
+
+    /// Returns wether the last frame was returned
+    pub fn is_done(&self) -> bool {
+        self.shown >= self.frames
+    }
+
+    /// Returns the next frame, the text is repeated once all digits settled
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        self.shown = (self.shown + 1).min(self.frames);
+        let mut frame = self.target;
+        for (cell, settle) in frame.iter_mut().zip(self.settle) {
+            if self.shown < settle {
+                let code = FontTable::ChatAt as u8 + (self.rng.next_u32() % FONT_LEN as u32) as u8;
+                *cell = FontTable::try_from(code).unwrap_or(*cell);
+            }
+        }
+        frame
+    }
+}
+
+/// Frames of the animation, ends after all digits settled
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::RngCore, const N: usize> Iterator for Matrix<R, N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done() {
+            return None;
+        }
+        Some(self.get_next_frame())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::RngCore, const N: usize> Animation for Matrix<R, N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    fn frame_ms(&self) -> u32 {
+        60
+    }
+}
+
+/// Snake running around the border of the display, drawn segment by segment
+///
+/// The snake moves along the top and bottom segments of all digits and the outer sides of the first and last digit.
+/// Every digit it passes needs a custom character, which is taken from a pool of CGRAM slots and rewritten
+/// for every frame. The pool needs a slot for every digit the snake covers at once, digits without a slot stay blank.
+///
+/// ``` text
+/// let mut snake = Snake::new(&[FontTable::Ram0, FontTable::Ram1, FontTable::Ram2, FontTable::Ram3]);
+/// loop {
+///     snake.draw_next(&mut vfd)?;
+///     delay.delay_ms(snake.frame_ms());
+/// }
+/// ```
+pub struct Snake<'a, const N: usize = NUM_DIGITS> {
+    slots: &'a [FontTable],
+    len: usize,
+    frame_ms: u32,
+    head: usize,
+    assigned: [Option<usize>; N],
+}
+
+impl<'a> Snake<'a> {
+    /// Snake with a length of three segments, drawn with the CGRAM `slots`
+    pub fn new(slots: &'a [FontTable]) -> Self {
+        Self::new_with_digits(slots)
+    }
+}
+
+impl<'a, const N: usize> Snake<'a, N> {
+    /// Same as [new()](Snake::new()) for a display with `N` digits
+    pub fn new_with_digits(slots: &'a [FontTable]) -> Self {
+        Snake {
+            slots,
+            len: 3,
+            frame_ms: 60,
+            head: 0,
+            assigned: [None; N],
+        }
+    }
+
+    /// Set the length of the snake in segments (at least one)
+    pub fn with_length(mut self, len: usize) -> Self {
+        self.len = len.max(1);
+        self
+    }
+
+    /// Set the time the snake stays on one position in milliseconds
+    pub fn with_frame_ms(mut self, frame_ms: u32) -> Self {
+        self.frame_ms = frame_ms;
+        self
+    }
+
+    /// Suggested time between two calls of [draw_next()](Self::draw_next())
+    pub fn frame_ms(&self) -> u32 {
+        self.frame_ms
+    }
+
+    /// Number of segments of the path around the display
+    const PATH_LEN: usize = 2 * N + 4;
+
+    /// Digit and segment of the path at `pos`, clockwise starting at the top left
+    fn segment(pos: usize) -> (usize, Segments) {
+        match pos {
+            p if p < N => (p, Segments::A),
+            p if p == N => (N - 1, Segments::B),
+            p if p == N + 1 => (N - 1, Segments::C),
+            p if p < 2 * N + 2 => (2 * N + 1 - p, Segments::D),
+            p if p == 2 * N + 2 => (0, Segments::E),
+            _ => (0, Segments::F),
+        }
+    }
+
+    /// Move the snake by one segment and draw it
+    ///
+    /// Writes the patterns of all digits the snake covers to CGRAM, followed by the whole display.
+    pub fn draw_next<IF, RstPin, VdonPin, Delay>(
+        &mut self,
+        vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let mut lit = [Segments::NONE; N];
+        for k in 0..self.len.min(Self::PATH_LEN) {
+            let (digit, segments) =
+                Self::segment((self.head + Self::PATH_LEN - k) % Self::PATH_LEN);
+            lit[digit] |= segments;
+        }
+        self.head = (self.head + 1) % Self::PATH_LEN;
+
+        for (slot, segments) in self.assigned.iter_mut().zip(lit) {
+            if segments == Segments::NONE {
+                *slot = None;
+            }
+        }
+        let mut frame = [FontTable::CharSpace; N];
+        for (digit, segments) in lit.into_iter().enumerate() {
+            if segments == Segments::NONE {
+                continue;
+            }
+            let slot = match self.assigned[digit] {
+                Some(slot) => slot,
+                None => {
+                    let Some(free) =
+                        (0..self.slots.len()).find(|i| !self.assigned.contains(&Some(*i)))
+                    else {
+                        continue;
+                    };
+                    self.assigned[digit] = Some(free);
+                    free
+                }
+            };
+            vfd.set_cgram_pattern(self.slots[slot], Glyph::from_segments(segments))?;
+            frame[digit] = self.slots[slot];
+        }
+        vfd.display(frame)
+    }
+}
+
+/// Direction of a [VerticalRoll]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RollDirection {
+    /// The new character comes in from the bottom
+    Up,
+    /// The new character comes in from the top, like a split-flap display
+    #[default]
+    Down,
+}
+
+/// Shape of the characters supported by [VerticalRoll] by default: digits, space and `-`
+pub fn default_shape(c: char) -> Option<Glyph> {
+    match c {
+        ' ' => Some(Glyph::EMPTY),
+        '-' => Some(Glyph::builder().center_h()),
+        c => glyphs::digit(c),
+    }
+}
+
+/// Upper half of a character cell without the center bar
+const TOP_HALF: Segments = Segments::from_bits(
+    Segments::A
+        .union(Segments::F)
+        .union(Segments::H)
+        .union(Segments::I)
+        .union(Segments::J)
+        .union(Segments::B)
+        .bits(),
+);
+/// Lower half of a character cell without the center bar
+const BOTTOM_HALF: Segments = TOP_HALF.mirror_vertical();
+
+/// One-shot transition rolling every changed digit vertically to its new character
+///
+/// The intermediate steps combine one half of the old character with the other half of the new one.
+/// They are custom characters, every changing digit takes one slot of the CGRAM pool for the duration of the roll.
+/// Digits without a free slot or without a known [shape](Self::with_shapes()) change directly.
+///
+/// ``` text
+/// let mut roll = VerticalRoll::new("12:59", "13:00", &[FontTable::Ram0, FontTable::Ram1, FontTable::Ram2]);
+/// while !roll.is_done() {
+///     roll.draw_next(&mut vfd)?;
+///     delay.delay_ms(roll.frame_ms());
+/// }
+/// ```
+pub struct VerticalRoll<'a, const N: usize = NUM_DIGITS> {
+    from: [char; N],
+    to: [char; N],
+    slots: &'a [FontTable],
+    direction: RollDirection,
+    shapes: fn(char) -> Option<Glyph>,
+    frame_ms: u32,
+    shown: usize,
+}
+
+impl<'a> VerticalRoll<'a> {
+    /// Roll from the text `from` to `to`, both starting at the leftmost digit
+    pub fn new(from: &str, to: &str, slots: &'a [FontTable]) -> Self {
+        Self::new_with_digits(from, to, slots)
+    }
+}
+
+impl<'a, const N: usize> VerticalRoll<'a, N> {
+    /// Number of frames including the final one
+    const FRAMES: usize = 3;
+
+    /// Same as [new()](VerticalRoll::new()) for a display with `N` digits
+    pub fn new_with_digits(from: &str, to: &str, slots: &'a [FontTable]) -> Self {
+        let chars = |text: &str| {
+            let mut chars = text.chars();
+            core::array::from_fn(|_| chars.next().unwrap_or(' '))
+        };
+        VerticalRoll {
+            from: chars(from),
+            to: chars(to),
+            slots,
+            direction: RollDirection::Down,
+            shapes: default_shape,
+            frame_ms: 80,
+            shown: 0,
+        }
+    }
+
+    /// Set the direction the characters move
+    pub fn with_direction(mut self, direction: RollDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Look up the segments of characters with `shapes` instead of [default_shape()]
+    ///
+    /// The shapes should resemble the built-in font, as the roll ends with the built-in character.
+    pub fn with_shapes(mut self, shapes: fn(char) -> Option<Glyph>) -> Self {
+        self.shapes = shapes;
+        self
+    }
+
+    /// Set the time between two frames in milliseconds
+    pub fn with_frame_ms(mut self, frame_ms: u32) -> Self {
+        self.frame_ms = frame_ms;
+        self
+    }
+
+    /// Suggested time between two calls of [draw_next()](Self::draw_next())
+    pub fn frame_ms(&self) -> u32 {
+        self.frame_ms
+    }
+
+    /// Returns wether the final frame was drawn
+    pub fn is_done(&self) -> bool {
+        self.shown >= Self::FRAMES
+    }
+
+    /// Draw the next step of the roll, the new text is drawn again once the roll is done
+    pub fn draw_next<IF, RstPin, VdonPin, Delay>(
+        &mut self,
+        vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        self.shown = (self.shown + 1).min(Self::FRAMES);
+        let mut frame = self.to.map(FontTable::from);
+        if self.shown < Self::FRAMES {
+            let leading = match self.direction {
+                RollDirection::Up => BOTTOM_HALF,
+                RollDirection::Down => TOP_HALF,
+            };
+            // The center bar follows in the second step
+            let mask = match self.shown {
+                1 => leading,
+                _ => leading.union(Segments::G),
+            };
+            let mut slots = self.slots.iter();
+            for (digit, cell) in frame.iter_mut().enumerate() {
+                let (from, to) = (self.from[digit], self.to[digit]);
+                if from == to {
+                    continue;
+                }
+                let (Some(old), Some(new)) = ((self.shapes)(from), (self.shapes)(to)) else {
+                    continue;
+                };
+                let Some(slot) = slots.next() else {
+                    continue;
+                };
+                let new = new.segments().difference(Segments::ALL.difference(mask));
+                let old = old.segments().difference(mask);
+                vfd.set_cgram_pattern(*slot, Glyph::from_segments(new.union(old)))?;
+                *cell = *slot;
+            }
+        }
+        vfd.display(frame)
+    }
+}
+
+/// Progress curve of a [CountTo]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Fast start, slowing down towards the target
+    EaseOut,
+    /// Slow start and end, fastest in the middle
+    EaseInOut,
+}
+
+impl Easing {
+    /// Progress after `frame` of `frames` frames as fraction of `frames²`
+    fn progress(self, frame: u32, frames: u32) -> u64 {
+        let (p, f) = (frame as u64, frames as u64);
+        match self {
+            Easing::Linear => p * f,
+            Easing::EaseOut => f * f - (f - p) * (f - p),
+            Easing::EaseInOut if 2 * p < f => 2 * p * p,
+            Easing::EaseInOut => f * f - 2 * (f - p) * (f - p),
+        }
+    }
+}
+
+/// Number counting from one value to another, e.g. for scores or sensor readings
+///
+/// [set_target()](Self::set_target()) starts counting from the current value, so readings can be updated
+/// while the previous count is still running.
+///
+/// ``` text
+/// let mut score = CountTo::new(0, 1500, 30).with_easing(Easing::EaseOut);
+/// vfd.run_animation(&mut score, None, 30)?;
+/// ```
+pub struct CountTo<const N: usize = NUM_DIGITS> {
+    from: i32,
+    to: i32,
+    frames: u32,
+    frame: u32,
+    easing: Easing,
+    format: IntFormat,
+}
+
+impl CountTo {
+    /// Count from `from` to `to` in `frames` frames
+    pub fn new(from: i32, to: i32, frames: u32) -> Self {
+        Self::new_with_digits(from, to, frames)
+    }
+}
+
+impl<const N: usize> CountTo<N> {
+    /// Same as [new()](CountTo::new()) for a display with `N` digits
+    pub fn new_with_digits(from: i32, to: i32, frames: u32) -> Self {
+        CountTo {
+            from,
+            to,
+            frames: frames.max(1),
+            frame: 0,
+            easing: Easing::Linear,
+            format: IntFormat::default(),
+        }
+    }
+
+    /// Set the progress curve
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set the format of the number, like for [display_int()](HCS12SS59T::display_int())
+    pub fn with_format(mut self, format: IntFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Count from the current value to `to` in `frames` frames
+    pub fn set_target(&mut self, to: i32, frames: u32) {
+        self.from = self.value();
+        self.to = to;
+        self.frames = frames.max(1);
+        self.frame = 0;
+    }
+
+    /// The value of the last frame
+    pub fn value(&self) -> i32 {
+        let diff = self.to as i64 - self.from as i64;
+        let total = self.frames as u64 * self.frames as u64;
+        let progress = self.easing.progress(self.frame, self.frames);
+        (self.from as i64 + (diff as i128 * progress as i128 / total as i128) as i64) as i32
+    }
+
+    /// Returns wether the target is reached
+    pub fn is_done(&self) -> bool {
+        self.frame >= self.frames
+    }
+
+    /// Advance by one frame and return the value, the target is repeated once it is reached
+    pub fn get_next_value(&mut self) -> i32 {
+        self.frame = (self.frame + 1).min(self.frames);
+        self.value()
+    }
+
+    /// Like [get_next_value()](Self::get_next_value()), but returns the formatted frame
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        let value = self.get_next_value();
+        let text = format::format_int(value < 0, value.unsigned_abs(), self.format);
+        let padding = self.format.align.padding(text.as_str().chars().count(), N);
+        to_frame(
+            core::iter::repeat_n(' ', padding).chain(text.as_str().chars()),
+            ' ',
+        )
+    }
+}
+
+/// Frames of the count, ends with the target
+impl<const N: usize> Iterator for CountTo<N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done() {
+            return None;
+        }
+        Some(self.get_next_frame())
+    }
+}
+
+impl<const N: usize> Animation for CountTo<N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    fn frame_ms(&self) -> u32 {
+        50
+    }
+}
+
+/// Pages through all characters of the font, see [demo_font()]
+pub struct FontDemo<const N: usize = NUM_DIGITS> {
+    page: usize,
+}
+
+/// Animation showing every [FontTable] character, `N` (default 12) per page
+///
+/// Starts with the built-in font, followed by the 16 custom characters. Useful to compare the font with the datasheet
+/// and to find dead segments. Pages repeat endlessly, [pages()](FontDemo::pages()) frames show each character once:
+///
+/// ``` text
+/// let mut demo = demo_font();
+/// vfd.run_animation(&mut demo, Some(2000), demo.pages())?;
+/// ```
+pub fn demo_font() -> FontDemo {
+    FontDemo::new_with_digits()
+}
+
+impl<const N: usize> FontDemo<N> {
+    /// Number of characters in the font table
+    const CHARS: usize = FONT_LEN as usize + 16;
+
+    /// Same as [demo_font()] for a display with `N` digits
+    pub fn new_with_digits() -> Self {
+        FontDemo { page: 0 }
+    }
+
+    /// Number of pages needed to show every character once
+    pub fn pages(&self) -> u32 {
+        Self::CHARS.div_ceil(N.max(1)) as u32
+    }
+
+    /// Returns the next page, the last page is padded with blanks
+    pub fn get_next_frame(&mut self) -> [FontTable; N] {
+        let first = self.page * N;
+        self.page = (self.page + 1) % self.pages() as usize;
+        core::array::from_fn(|i| {
+            let idx = first + i;
+            if idx >= Self::CHARS {
+                return FontTable::CharSpace;
+            }
+            // The built-in font starts at 0x10 and is followed by the custom characters at 0x00
+            let code = (idx as u8 + FontTable::ChatAt as u8) % (FONT_LEN + 16);
+            FontTable::try_from(code).unwrap_or(FontTable::CharSpace)
+        })
+    }
+}
+
+/// Endless sequence of pages
+impl<const N: usize> Iterator for FontDemo<N> {
+    type Item = [FontTable; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.get_next_frame())
+    }
+}
+
+impl<const N: usize> Animation for FontDemo<N> {
+    fn next_frame(&mut self) -> impl IntoIterator<Item = FontTable> {
+        self.get_next_frame()
+    }
+
+    fn frame_ms(&self) -> u32 {
+        2000
+    }
+}
+
+/// Blinking of the whole display
+///
+/// Switches the segments off and on with the lights command, the display RAM is untouched,
+/// so a scroller can keep updating the content while it blinks.
+/// Advance it with [tick()](Self::tick()) from the main loop, or let the driver manage it with
+/// [blink_display()](HCS12SS59T::blink_display()).
+pub struct Blink {
+    period_ms: u32,
+    on_ms: u32,
+    started: Option<u32>,
+    lit: bool,
+}
+
+impl Blink {
+    /// Blink with `period_ms`, the display is lit for `duty` percent of the period
+    pub fn new(period_ms: u32, duty: u8) -> Self {
+        Self {
+            period_ms: period_ms.max(1),
+            on_ms: (period_ms as u64 * duty.min(100) as u64 / 100) as u32,
+            started: None,
+            lit: true,
+        }
+    }
+
+    /// Start blinking, beginning with the lit phase
+    pub fn start(&mut self, now_ms: u32) {
+        self.started = Some(now_ms);
+    }
+
+    /// Stop blinking and light the display again
+    pub fn stop<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &mut self,
+        vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        self.started = None;
+        if !self.lit {
+            vfd.set_lights(Lights::Normal)?;
+            self.lit = true;
+        }
+        Ok(())
+    }
+
+    /// Returns wether blinking is started
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Switch the display on or off according to the phase at `now_ms`
+    ///
+    /// Only sends a command when the phase changed.
+    pub fn tick<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &mut self,
+        vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+        now_ms: u32,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let Some(started) = self.started else {
+            return Ok(());
+        };
+        let lit = now_ms.wrapping_sub(started) % self.period_ms < self.on_ms;
+        if lit != self.lit {
+            vfd.set_lights(if lit { Lights::Normal } else { Lights::Off })?;
+            self.lit = lit;
+        }
+        Ok(())
+    }
+}
+
+/// Boot screen: a lamp test sweeping over the display, followed by a name fading in
+///
+/// The sweep uses a CGRAM slot ([RamF](FontTable::RamF) by default), which is overwritten.
+/// Load custom characters after the splash or choose another slot.
+pub struct Splash<'a> {
+    name: &'a str,
+    slot: FontTable,
+    step_ms: u32,
+    hold_ms: u32,
+    fade_ms: u32,
+}
+
+impl<'a> Splash<'a> {
+    /// Splash showing `name` centered at the end
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            slot: FontTable::RamF,
+            step_ms: 40,
+            hold_ms: 300,
+            fade_ms: 800,
+        }
+    }
+
+    /// Set the CGRAM slot used for the lamp test
+    pub fn slot(mut self, slot: FontTable) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    /// Set the time per digit of the sweep in milliseconds
+    pub fn step_ms(mut self, step_ms: u32) -> Self {
+        self.step_ms = step_ms;
+        self
+    }
+
+    /// Set the time all segments stay lit in milliseconds
+    pub fn hold_ms(mut self, hold_ms: u32) -> Self {
+        self.hold_ms = hold_ms;
+        self
+    }
+
+    /// Set the duration of the fade-in of the name in milliseconds
+    pub fn fade_ms(mut self, fade_ms: u32) -> Self {
+        self.fade_ms = fade_ms;
+        self
+    }
+
+    /// Play the splash, blocking with the delay of the driver
+    ///
+    /// Ends with the name shown at the brightness from before the splash.
+    pub fn play<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &self,
+        vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let blank = FontTable::CharSpace;
+        vfd.set_cgram_pattern(self.slot, glyphs::BLOCK)?;
+        for lit in 1..=N {
+            vfd.display(core::iter::repeat_n(self.slot, lit).chain(core::iter::repeat(blank)))?;
+            vfd.delay.delay_ms(self.step_ms);
+        }
+        vfd.delay.delay_ms(self.hold_ms);
+        for dark in 1..=N {
+            vfd.display(core::iter::repeat_n(blank, dark).chain(core::iter::repeat(self.slot)))?;
+            vfd.delay.delay_ms(self.step_ms);
+        }
+
+        let brightness = vfd.brightness;
+        vfd.set_duty(1)?;
+        vfd.display_aligned(self.name, Alignment::Center, blank)?;
+        vfd.fade_to(brightness, self.fade_ms)
+    }
+}
+
+/// Play `frames` frames of an animation, waiting `frame_ms` after each one
+///
+/// Blocks until all frames are shown, the delay of the driver is used for the timing.
+/// With `None` every frame is shown for its own [Animation::frame_ms()], which keeps the per-frame durations
+/// of e.g. [FrameAnimation] and [MarkupText]. `Some` shows all frames for the same time.
+/// After the first frame only the characters that changed are transmitted, which keeps the bus mostly idle
+/// for clocks and counters.
+pub fn run<IF, RstPin, VdonPin, Delay, const N: usize, A>(
+    vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    animation: &mut A,
+    frame_ms: Option<u32>,
+    frames: u32,
+) -> Result<(), Error>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+    A: Animation,
+{
+    let mut shown = None;
+    for _ in 0..frames {
+        vfd.display_changes(&mut shown, animation.next_frame())?;
+        vfd.delay
+            .delay_ms(frame_ms.unwrap_or_else(|| animation.frame_ms()));
+    }
+    Ok(())
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Play an animation, see [animation::run()](run())
+    pub fn run_animation<A: Animation>(
+        &mut self,
+        animation: &mut A,
+        frame_ms: Option<u32>,
+        frames: u32,
+    ) -> Result<(), Error> {
+        run(self, animation, frame_ms, frames)
+    }
+
+    /// Blink the whole display with `period_ms`, lit for `duty` percent of the period
+    ///
+    /// Works like [Blink], but is kept by the driver and advanced with [tick()](Self::tick()).
+    /// Blinking starts with the lit phase on the next tick.
+    pub fn blink_display(&mut self, period_ms: u32, duty: u8) {
+        self.blink = Some(Blink::new(period_ms, duty));
+    }
+
+    /// Stop blinking and light the display again
+    pub fn stop_blink(&mut self) -> Result<(), Error> {
+        match self.blink.take() {
+            Some(mut blink) => blink.stop(self),
+            None => Ok(()),
+        }
+    }
+
+    /// Advance the [blinking](Self::blink_display()) of the display
+    ///
+    /// Only sends a command when the display has to be switched on or off.
+    pub fn tick(&mut self, now_ms: u32) -> Result<(), Error> {
+        let Some(mut blink) = self.blink.take() else {
+            return Ok(());
+        };
+        if !blink.is_running() {
+            blink.start(now_ms);
+        }
+        let result = blink.tick(self, now_ms);
+        self.blink = Some(blink);
+        result
+    }
+
+    /// Play the default [Splash] with `name`, e.g. right after [init()](HCS12SS59T::init())
+    pub fn splash(&mut self, name: &str) -> Result<(), Error> {
+        Splash::new(name).play(self)
+    }
+}
+
+/// Play an animation forever, waiting with an async delay between the frames
+///
+/// Owns the driver, so it can be the body of an Embassy task. The driver keeps using its blocking delay
+/// for the (microsecond) bus timing, `delay` is only used between frames.
+/// Any [Animation] can be played, e.g. a [ScrollingText] configured with [ScrollBuilder].
+/// `updates` is polled once per frame and replaces the animation when it returns `Some`,
+/// e.g. `|| receiver.try_receive().ok()` with a channel.
+/// Like with [run()] every frame is shown for its [Animation::frame_ms()] with `None` for `frame_ms`,
+/// and only the characters that changed are transmitted.
+///
+/// Only returns on errors.
+#[cfg(feature = "async")]
+pub async fn animate_forever<IF, RstPin, VdonPin, Delay, AsyncDelay, const N: usize, A>(
+    mut vfd: HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    mut delay: AsyncDelay,
+    frame_ms: Option<u32>,
+    mut animation: A,
+    mut updates: impl FnMut() -> Option<A>,
+) -> Result<core::convert::Infallible, Error>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+    AsyncDelay: embedded_hal_async::delay::DelayNs,
+    A: Animation,
+{
+    let mut shown = None;
+    loop {
+        if let Some(next) = updates() {
+            animation = next;
+        }
+        vfd.display_changes(&mut shown, animation.next_frame())?;
+        delay
+            .delay_ms(frame_ms.unwrap_or_else(|| animation.frame_ms()))
+            .await;
+    }
+}
@@ -0,0 +1,179 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::interface::Interface;
+use super::{
+    AdramBits, CharMapper, Command, Error, FontTable, CGRAM_SLOTS, DCRAM_SIZE, HCS12SS59T,
+};
+
+/// CGRAM patterns fitting into a frame of command and full DCRAM length
+const PATTERNS_PER_FRAME: usize = DCRAM_SIZE / 2;
+
+/// Runs of consecutive addresses in `set`, as start address and length
+fn runs(mut set: u16) -> impl Iterator<Item = (usize, usize)> {
+    core::iter::from_fn(move || {
+        if set == 0 {
+            return None;
+        }
+        let start = set.trailing_zeros() as usize;
+        let len = (set >> start).trailing_ones() as usize;
+        set &= !((((1_u32 << len) - 1) << start) as u16);
+        Some((start, len))
+    })
+}
+
+/// Queued commands of [HCS12SS59T::with_batch()]
+///
+/// Nothing is sent while the batch is filled. Afterwards the changes are emitted back-to-back,
+/// consecutive addresses of the CGRAM, DCRAM and ADRAM are merged into a single write each,
+/// so a run of changes costs one CS cycle with its setup and hold instead of one per change.
+pub struct Batch<const N: usize> {
+    fill: FontTable,
+    mapper: &'static dyn CharMapper,
+    dcram: [u8; N],
+    dcram_set: u16,
+    adram: [u8; N],
+    adram_set: u16,
+    cgram: [[u8; 2]; CGRAM_SLOTS],
+    cgram_set: u16,
+    brightness: Option<u8>,
+}
+
+impl<const N: usize> Batch<N> {
+    fn new(fill: FontTable, mapper: &'static dyn CharMapper) -> Self {
+        Self {
+            fill,
+            mapper,
+            dcram: [0; N],
+            dcram_set: 0,
+            adram: [0; N],
+            adram_set: 0,
+            cgram: [[0; 2]; CGRAM_SLOTS],
+            cgram_set: 0,
+            brightness: None,
+        }
+    }
+
+    /// Queue a single character, see [HCS12SS59T::set_char()]
+    pub fn set_char<C: Into<FontTable>>(&mut self, addr: u8, char: C) -> Result<(), Error> {
+        let addr = addr as usize;
+        if addr >= N {
+            return Err(Error::InvalidInput);
+        }
+        self.dcram[addr] = char.into() as u8;
+        self.dcram_set |= 1 << addr;
+        Ok(())
+    }
+
+    /// Queue text for the whole display, see [HCS12SS59T::display()]
+    pub fn display<T>(&mut self, text: T)
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        let mut text = text.into_iter();
+        for addr in (0..N).rev() {
+            let c = text.next().map_or(self.fill, Into::into);
+            self.dcram[addr] = c as u8;
+        }
+        self.dcram_set = ((1_u32 << N) - 1) as u16;
+    }
+
+    /// Queue a string for the whole display, see [HCS12SS59T::display_str()]
+    pub fn display_str(&mut self, text: &str) {
+        let mapper = self.mapper;
+        self.display(text.chars().map(|c| mapper.map(c)))
+    }
+
+    /// Queue auxiliary segment bits, see [HCS12SS59T::set_adram()]
+    pub fn set_adram<A: Into<AdramBits>>(&mut self, addr: u8, value: A) -> Result<(), Error> {
+        let addr = addr as usize;
+        if addr >= N {
+            return Err(Error::InvalidInput);
+        }
+        self.adram[addr] = value.into().bits();
+        self.adram_set |= 1 << addr;
+        Ok(())
+    }
+
+    /// Queue a CGRAM pattern, see [HCS12SS59T::set_cgram_pattern()]
+    pub fn set_cgram_pattern<P: Into<[u8; 2]>>(
+        &mut self,
+        addr: FontTable,
+        pattern: P,
+    ) -> Result<(), Error> {
+        let pattern = pattern.into();
+        let slot = addr as usize;
+        if slot >= CGRAM_SLOTS {
+            return Err(Error::InvalidInput);
+        }
+        self.cgram[slot] = pattern;
+        self.cgram_set |= 1 << slot;
+        Ok(())
+    }
+
+    /// Queue a brightness change, see [HCS12SS59T::brightness()]
+    ///
+    /// The brightness is applied after all other changes.
+    pub fn brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        if brightness > 15 {
+            return Err(Error::InvalidInput);
+        }
+        self.brightness = Some(brightness);
+        Ok(())
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Queue several commands and send them together
+    ///
+    /// The closure fills a [Batch], afterwards CGRAM patterns, characters, ADRAM bits and the brightness
+    /// are sent in this order, with runs of consecutive addresses written as one frame.
+    /// Nothing is sent if the closure returns an error.
+    pub fn with_batch<R>(
+        &mut self,
+        f: impl FnOnce(&mut Batch<N>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let mut batch = Batch::new(self.fill, self.mapper);
+        let result = f(&mut batch)?;
+
+        for (start, len) in runs(batch.cgram_set) {
+            // At most 8 patterns per frame, the 17 bytes a transport without CS-Pin can write at once
+            let end = start + len;
+            for start in (start..end).step_by(PATTERNS_PER_FRAME) {
+                let len = PATTERNS_PER_FRAME.min(end - start);
+                let mut data = [0_u8; DCRAM_SIZE + 1];
+                data[0] = Command::CGRamWrite as u8 | start as u8;
+                for (bytes, pattern) in data[1..]
+                    .chunks_exact_mut(2)
+                    .zip(&batch.cgram[start..start + len])
+                {
+                    bytes.copy_from_slice(pattern);
+                }
+                self.write_buf(&data[..=2 * len])?;
+            }
+        }
+
+        for (start, len) in runs(batch.dcram_set) {
+            self.write_dcram(start as u8, &batch.dcram[start..start + len])?;
+        }
+
+        for (start, len) in runs(batch.adram_set) {
+            let mut data = [0_u8; DCRAM_SIZE + 1];
+            data[0] = Command::ADRamWrite as u8 | start as u8;
+            data[1..=len].copy_from_slice(&batch.adram[start..start + len]);
+            self.write_buf(&data[..=len])?;
+        }
+
+        if let Some(brightness) = batch.brightness {
+            self.brightness(brightness)?;
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,101 @@
+use super::{Error, FontTable, NUM_DIGITS};
+
+/// Content of the whole display, composed off-device
+///
+/// Positions count from the leftmost digit, independent of the reversed DCRAM addresses.
+/// Show it with [HCS12SS59T::display_buffer()](super::HCS12SS59T::display_buffer()).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DisplayBuffer<const N: usize = NUM_DIGITS>(pub [FontTable; N]);
+
+impl DisplayBuffer {
+    /// Blank buffer for the 12 digits of the HCS-12SS59T
+    pub const fn new() -> Self {
+        Self::new_with_digits()
+    }
+}
+
+impl Default for DisplayBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> DisplayBuffer<N> {
+    /// Blank buffer for `N` digits
+    pub const fn new_with_digits() -> Self {
+        Self([FontTable::CharSpace; N])
+    }
+
+    /// Character at `pos`, `None` if out of range
+    pub fn get(&self, pos: usize) -> Option<FontTable> {
+        self.0.get(pos).copied()
+    }
+
+    /// Set the character at `pos`
+    pub fn set<C: Into<FontTable>>(&mut self, pos: usize, char: C) -> Result<(), Error> {
+        let cell = self.0.get_mut(pos).ok_or(Error::InvalidInput)?;
+        *cell = char.into();
+        Ok(())
+    }
+
+    /// Set all positions to `char`
+    pub fn fill<C: Into<FontTable>>(&mut self, char: C) {
+        self.0 = [char.into(); N];
+    }
+
+    /// Move the content `n` positions to the left, blanks are shifted in on the right
+    pub fn shift_left(&mut self, n: usize) {
+        let n = n.min(N);
+        self.0.copy_within(n.., 0);
+        self.0[N - n..].fill(FontTable::CharSpace);
+    }
+
+    /// Move the content `n` positions to the right, blanks are shifted in on the left
+    pub fn shift_right(&mut self, n: usize) {
+        let n = n.min(N);
+        self.0.copy_within(..N - n, n);
+        self.0[..n].fill(FontTable::CharSpace);
+    }
+
+    /// Overwrite the characters from `pos` on with `text`, leaving the rest untouched
+    ///
+    /// Text beyond the last digit is dropped. Returns the number of characters written.
+    pub fn overwrite_from<T>(&mut self, pos: usize, text: T) -> usize
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        let cells = self.0.iter_mut().skip(pos);
+        cells.zip(text).map(|(cell, c)| *cell = c.into()).count()
+    }
+
+    /// Font codes of the buffer, leftmost digit first
+    pub fn to_bytes(&self) -> [u8; N] {
+        self.0.map(|c| c as u8)
+    }
+}
+
+impl<const N: usize> From<[FontTable; N]> for DisplayBuffer<N> {
+    fn from(value: [FontTable; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> IntoIterator for DisplayBuffer<N> {
+    type Item = FontTable;
+    type IntoIter = core::array::IntoIter<FontTable, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Writes the characters from left to right, CGRAM slots are shown as `�`
+impl<const N: usize> core::fmt::Display for DisplayBuffer<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|c| core::fmt::Display::fmt(c, f))
+    }
+}
@@ -0,0 +1,178 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::SpiDevice;
+
+use super::interface::{Interface, SpiInterface};
+use super::state::Uninitialized;
+use super::{
+    CharMapper, DefaultMapper, FontTable, NoPin, Polarity, Timing, HCS12SS59T, NUM_DIGITS,
+};
+
+/// Builder for [HCS12SS59T]
+///
+/// Created with [HCS12SS59T::builder()], all settings are optional.
+/// `N` is the number of digits, see [digits()](Self::digits()).
+pub struct Builder<VdonPin, const N: usize = NUM_DIGITS> {
+    n_vdon: Option<VdonPin>,
+    reset_polarity: Polarity,
+    vdon_polarity: Polarity,
+    brightness: u8,
+    brightness_ramp_ms: u32,
+    timing: Timing,
+    fill: FontTable,
+    mapper: &'static dyn CharMapper,
+}
+
+impl Builder<NoPin> {
+    pub(crate) fn new() -> Self {
+        Self {
+            n_vdon: None,
+            reset_polarity: Polarity::ActiveLow,
+            vdon_polarity: Polarity::ActiveLow,
+            brightness: 7,
+            brightness_ramp_ms: 0,
+            timing: Timing::default(),
+            fill: FontTable::CharSpace,
+            mapper: &DefaultMapper,
+        }
+    }
+}
+
+impl<VdonPin, const N: usize> Builder<VdonPin, N> {
+    /// Set the pin switching the supply voltage
+    ///
+    /// The pin is active low by default, see [vdon_polarity()](Self::vdon_polarity()).
+    pub fn vdon<P: OutputPin>(self, n_vdon: P) -> Builder<P, N> {
+        Builder {
+            n_vdon: Some(n_vdon),
+            reset_polarity: self.reset_polarity,
+            vdon_polarity: self.vdon_polarity,
+            brightness: self.brightness,
+            brightness_ramp_ms: self.brightness_ramp_ms,
+            timing: self.timing,
+            fill: self.fill,
+            mapper: self.mapper,
+        }
+    }
+
+    /// Set the active level of the reset pin, active low by default
+    pub fn reset_polarity(mut self, polarity: Polarity) -> Self {
+        self.reset_polarity = polarity;
+        self
+    }
+
+    /// Set the active level of the supply pin, active low by default
+    pub fn vdon_polarity(mut self, polarity: Polarity) -> Self {
+        self.vdon_polarity = polarity;
+        self
+    }
+
+    /// Set the brightness `1..=15` applied by [init()](HCS12SS59T::init())
+    ///
+    /// Values out of range are clamped silently: `0` becomes `1`, values above 15 become `15`.
+    /// To start dark call [blank()](HCS12SS59T::blank()) after the initialization.
+    pub fn brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness.clamp(1, 15);
+        self
+    }
+
+    /// Ramp brightness changes over `ramp_ms`, see [set_brightness_ramp()](HCS12SS59T::set_brightness_ramp())
+    pub fn brightness_ramp(mut self, ramp_ms: u32) -> Self {
+        self.brightness_ramp_ms = ramp_ms;
+        self
+    }
+
+    /// Set the number of connected digits (`1..=16`)
+    pub fn digits<const M: usize>(self) -> Builder<VdonPin, M> {
+        Builder {
+            n_vdon: self.n_vdon,
+            reset_polarity: self.reset_polarity,
+            vdon_polarity: self.vdon_polarity,
+            brightness: self.brightness,
+            brightness_ramp_ms: self.brightness_ramp_ms,
+            timing: self.timing,
+            fill: self.fill,
+            mapper: self.mapper,
+        }
+    }
+
+    /// Set the timing profile of the serial interface
+    pub fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Set the character used to pad text shorter than the display
+    pub fn fill<C: Into<FontTable>>(mut self, fill: C) -> Self {
+        self.fill = fill.into();
+        self
+    }
+
+    /// Set the [CharMapper] used to display strings
+    pub fn char_mapper(mut self, mapper: &'static dyn CharMapper) -> Self {
+        self.mapper = mapper;
+        self
+    }
+
+    /// Construct the driver
+    ///
+    /// The returned driver still has to be initialized with [init()](HCS12SS59T::init()).
+    pub fn build<SPI, RstPin, Delay, CsPin>(
+        self,
+        spi: SPI,
+        n_reset: Option<RstPin>,
+        delay: Delay,
+        cs: CsPin,
+    ) -> HCS12SS59T<SpiInterface<SPI, CsPin>, RstPin, VdonPin, Delay, N, Uninitialized>
+    where
+        SPI: SpiDevice,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        CsPin: OutputPin,
+        Delay: DelayNs,
+    {
+        self.build_with_interface(SpiInterface::new(spi, Some(cs)), n_reset, delay)
+    }
+
+    /// Construct the driver without a dedicated CS-Pin
+    ///
+    /// See [HCS12SS59T::new_without_cs()].
+    pub fn build_without_cs<SPI, RstPin, Delay>(
+        self,
+        spi: SPI,
+        n_reset: Option<RstPin>,
+        delay: Delay,
+    ) -> HCS12SS59T<SpiInterface<SPI, NoPin>, RstPin, VdonPin, Delay, N, Uninitialized>
+    where
+        SPI: SpiDevice,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        self.build_with_interface(SpiInterface::new(spi, None), n_reset, delay)
+    }
+
+    /// Construct the driver on top of an arbitrary [Interface]
+    pub fn build_with_interface<IF, RstPin, Delay>(
+        self,
+        interface: IF,
+        n_reset: Option<RstPin>,
+        delay: Delay,
+    ) -> HCS12SS59T<IF, RstPin, VdonPin, Delay, N, Uninitialized>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let mut driver = HCS12SS59T::with_interface(interface, n_reset, delay, self.n_vdon);
+        driver.reset_polarity = self.reset_polarity;
+        driver.vdon_polarity = self.vdon_polarity;
+        driver.timing = self.timing;
+        driver.fill = self.fill;
+        driver.mapper = self.mapper;
+        driver.init_brightness = self.brightness;
+        driver.brightness_ramp_ms = self.brightness_ramp_ms;
+        driver
+    }
+}
@@ -0,0 +1,80 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::interface::Interface;
+use super::{Error, FontTable, HCS12SS59T};
+
+/// Several displays combined into one wide virtual display
+///
+/// The displays are ordered from left to right, the chain behaves like a display with `M * N` digits.
+///
+/// For scrolling over the whole chain, create the [ScrollingText](super::animation::ScrollingText) with the full width,
+/// e.g. `ScrollingText::<Cycle, 36>::new_with_digits()` for three 12 digit displays.
+pub struct DisplayChain<IF, RstPin, VdonPin, Delay, const N: usize, const M: usize> {
+    displays: [HCS12SS59T<IF, RstPin, VdonPin, Delay, N>; M],
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize, const M: usize>
+    DisplayChain<IF, RstPin, VdonPin, Delay, N, M>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Number of digits of the whole chain
+    pub const WIDTH: usize = N * M;
+
+    /// Combines initialized displays, ordered from left to right
+    pub fn new(displays: [HCS12SS59T<IF, RstPin, VdonPin, Delay, N>; M]) -> Self {
+        Self { displays }
+    }
+
+    /// Returns the displays
+    pub fn release(self) -> [HCS12SS59T<IF, RstPin, VdonPin, Delay, N>; M] {
+        self.displays
+    }
+
+    /// Access the single displays, e.g. to set custom characters
+    pub fn displays(&mut self) -> &mut [HCS12SS59T<IF, RstPin, VdonPin, Delay, N>; M] {
+        &mut self.displays
+    }
+
+    /// Write text spanning all displays
+    ///
+    /// The text starts at the leftmost digit of the first display and is truncated to fit the chain.
+    pub fn display<T>(&mut self, text: T) -> Result<(), Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        let mut text = text.into_iter();
+        for display in &mut self.displays {
+            display.display(text.by_ref().take(N))?;
+        }
+        Ok(())
+    }
+
+    /// Write a string spanning all displays
+    ///
+    /// Same as [display()](Self::display()), but characters are mapped with the [CharMapper](crate::CharMapper)
+    /// of the display they end up on.
+    pub fn display_str(&mut self, text: &str) -> Result<(), Error> {
+        let mut text = text.chars();
+        for display in &mut self.displays {
+            let mapper = display.mapper;
+            display.display(text.by_ref().take(N).map(|c| mapper.map(c)))?;
+        }
+        Ok(())
+    }
+
+    /// Set the brightness of all displays
+    ///
+    /// See [HCS12SS59T::brightness()].
+    pub fn brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        for display in &mut self.displays {
+            display.brightness(brightness)?;
+        }
+        Ok(())
+    }
+}
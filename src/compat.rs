@@ -0,0 +1,145 @@
+//! Adapters for HALs implementing embedded-hal 0.2
+//!
+//! Pins and delays are wrapped in [Compat], SPI buses are used through [Spi02Interface]:
+//!
+//! ``` text
+//! let interface = Spi02Interface::new(spi, Compat(cs));
+//! let vfd = HCS12SS59T::builder()
+//!     .build_with_interface(interface, Some(Compat(n_reset)), Compat(delay))
+//!     .init()?;
+//! ```
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, Error as _, ErrorType, OutputPin};
+use embedded_hal_02::blocking::delay::DelayUs;
+use embedded_hal_02::blocking::spi::Write;
+use embedded_hal_02::digital::v2::OutputPin as OutputPin02;
+
+use super::interface::{FrameTiming, Interface, StepInterface};
+use super::Error;
+
+/// Wrapper implementing the embedded-hal 1.0 traits for an embedded-hal 0.2 pin or delay
+pub struct Compat<T>(pub T);
+
+/// Error of a wrapped embedded-hal 0.2 pin
+///
+/// The original error has no common type, it is reported as [ErrorKind::Other](digital::ErrorKind::Other).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompatError;
+
+impl digital::Error for CompatError {
+    fn kind(&self) -> digital::ErrorKind {
+        digital::ErrorKind::Other
+    }
+}
+
+impl<P: OutputPin02> ErrorType for Compat<P> {
+    type Error = CompatError;
+}
+
+impl<P: OutputPin02> OutputPin for Compat<P> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low().map_err(|_| CompatError)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high().map_err(|_| CompatError)
+    }
+}
+
+impl<D: DelayUs<u32>> DelayNs for Compat<D> {
+    /// Rounded up to whole microseconds
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_us(ns.div_ceil(1000));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us);
+    }
+}
+
+/// SPI transport for an embedded-hal 0.2 bus with a dedicated CS-Pin
+///
+/// Behaves like [SpiInterface](super::interface::SpiInterface) with a CS-Pin.
+pub struct Spi02Interface<SPI, CsPin> {
+    spi: SPI,
+    cs: CsPin,
+}
+
+impl<SPI, CsPin> Spi02Interface<SPI, CsPin>
+where
+    SPI: Write<u8>,
+    CsPin: OutputPin,
+{
+    /// Constructs a new SPI transport
+    pub fn new(spi: SPI, cs: CsPin) -> Self {
+        Self { spi, cs }
+    }
+
+    /// Returns the SPI bus and CS-Pin
+    pub fn release(self) -> (SPI, CsPin) {
+        (self.spi, self.cs)
+    }
+}
+
+impl<SPI, CsPin> Interface for Spi02Interface<SPI, CsPin>
+where
+    SPI: Write<u8>,
+    CsPin: OutputPin,
+{
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        self.write_frame_iter(frame.iter().copied(), timing, delay)
+    }
+
+    fn write_frame_iter<I, D>(
+        &mut self,
+        frame: I,
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = u8>,
+        D: DelayNs,
+    {
+        self.cs.set_low().map_err(|e| Error::ChipSelect(e.kind()))?;
+        delay.delay_us(timing.setup);
+        let written = frame.into_iter().try_for_each(|byte| {
+            self.spi.write(&[byte]).map_err(|_| Error::Spi)?;
+            if timing.gap > 0 {
+                delay.delay_us(timing.gap);
+            }
+            Ok(())
+        });
+        if written.is_ok() && timing.hold > 0 {
+            delay.delay_us(timing.hold);
+        }
+        // Release CS on errors as well, so the frame can be sent again
+        self.cs
+            .set_high()
+            .map_err(|e| Error::ChipSelect(e.kind()))?;
+        written
+    }
+}
+
+impl<SPI, CsPin> StepInterface for Spi02Interface<SPI, CsPin>
+where
+    SPI: Write<u8>,
+    CsPin: OutputPin,
+{
+    fn select(&mut self, selected: bool) -> Result<(), Error> {
+        if selected {
+            self.cs.set_low().map_err(|e| Error::ChipSelect(e.kind()))
+        } else {
+            self.cs.set_high().map_err(|e| Error::ChipSelect(e.kind()))
+        }
+    }
+
+    fn write_byte<D: DelayNs>(&mut self, byte: u8, _delay: &mut D) -> Result<(), Error> {
+        self.spi.write(&[byte]).map_err(|_| Error::Spi)
+    }
+}
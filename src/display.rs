@@ -0,0 +1,95 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::framebuffer::BufferedHCS12SS59T;
+use super::interface::Interface;
+use super::{DisplayChain, Error, HCS12SS59T};
+
+/// Line of segment digits showing text
+///
+/// Implemented by [HCS12SS59T], [BufferedHCS12SS59T], [DisplayChain] and the simulators of the `simulator` feature,
+/// so application code can be written once without naming all type parameters of the driver:
+///
+/// ``` text
+/// fn show_status(vfd: &mut impl SegmentTextDisplay, ok: bool) -> Result<(), Error> {
+///     vfd.set_brightness(if ok { 7 } else { 15 })?;
+///     vfd.write_line(if ok { "READY" } else { "CHECK SENSOR" })
+/// }
+/// ```
+pub trait SegmentTextDisplay {
+    /// Number of digits
+    fn width(&self) -> usize;
+
+    /// Show a line of text, truncated to fit and padded with the fill character
+    fn write_line(&mut self, text: &str) -> Result<(), Error>;
+
+    /// Set the brightness `1..=15`, `0` blanks the display
+    ///
+    /// Values above 15 are rejected with [Error::InvalidInput].
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Error>;
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> SegmentTextDisplay
+    for HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    fn width(&self) -> usize {
+        N
+    }
+
+    fn write_line(&mut self, text: &str) -> Result<(), Error> {
+        self.display_str(text)
+    }
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        self.brightness(brightness)
+    }
+}
+
+/// Writes the buffer and flushes it right away
+impl<IF, RstPin, VdonPin, Delay, const N: usize> SegmentTextDisplay
+    for BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    fn width(&self) -> usize {
+        N
+    }
+
+    fn write_line(&mut self, text: &str) -> Result<(), Error> {
+        self.display_str(text);
+        self.flush()
+    }
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        self.driver().brightness(brightness)
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize, const M: usize> SegmentTextDisplay
+    for DisplayChain<IF, RstPin, VdonPin, Delay, N, M>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    fn width(&self) -> usize {
+        Self::WIDTH
+    }
+
+    fn write_line(&mut self, text: &str) -> Result<(), Error> {
+        self.display_str(text)
+    }
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        self.brightness(brightness)
+    }
+}
@@ -0,0 +1,102 @@
+//! Async transfers with owned buffers, for HALs whose DMA takes ownership of the buffer
+//!
+//! The frame is encoded directly into the buffer handed to the [DmaWrite] implementation,
+//! which returns the buffer after the transfer:
+//!
+//! ``` text
+//! static FRAME: StaticCell<[u8; 13]> = StaticCell::new();
+//! let mut buf = FRAME.init([0; 13]);
+//! loop {
+//!     let (returned, result) = vfd.display_str_dma(&mut dma_spi, buf, "HELLO").await;
+//!     result?;
+//!     buf = returned;
+//! }
+//! ```
+//!
+//! CS and timing are left to the implementation. A DMA transfer can't pause between bytes, so the byte gap
+//! of the [Timing](crate::Timing) is not applied, the SPI clock has to be slow enough for the display.
+
+use embedded_dma::{ReadBuffer, WriteBuffer};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::interface::Interface;
+use super::{Command, Error, FontTable, HCS12SS59T};
+
+/// Async SPI write taking ownership of the buffer
+///
+/// Implementations select the chip, write the whole buffer and release the chip before returning the buffer,
+/// also if the transfer failed.
+#[allow(async_fn_in_trait)]
+pub trait DmaWrite {
+    /// Write all bytes of `buf` as one frame
+    async fn write_dma<B: ReadBuffer<Word = u8>>(&mut self, buf: B) -> (B, Result<(), Error>);
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Write a frame (command and data) prepared in an owned buffer
+    pub async fn write_buf_dma<S, B>(&mut self, spi: &mut S, buf: B) -> (B, Result<(), Error>)
+    where
+        S: DmaWrite,
+        B: ReadBuffer<Word = u8>,
+    {
+        #[cfg(feature = "trace")]
+        {
+            // SAFETY: the buffer is owned and not part of a transfer yet
+            let (ptr, len) = unsafe { buf.read_buffer() };
+            super::trace_frame(unsafe { core::slice::from_raw_parts(ptr, len) });
+        }
+        spi.write_dma(buf).await
+    }
+
+    /// Encode text into the owned buffer and write it to the display RAM, see [display()](Self::display())
+    ///
+    /// The buffer has to be exactly `N + 1` bytes long, longer frames would wrap around in the display RAM.
+    pub async fn display_dma<S, B, T>(
+        &mut self,
+        spi: &mut S,
+        mut buf: B,
+        text: T,
+    ) -> (B, Result<(), Error>)
+    where
+        S: DmaWrite,
+        B: ReadBuffer<Word = u8> + WriteBuffer<Word = u8>,
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        // SAFETY: the buffer is owned and not part of a transfer yet
+        let (ptr, len) = unsafe { buf.write_buffer() };
+        if len != N + 1 {
+            return (buf, Err(Error::InvalidInput));
+        }
+        let frame = unsafe { core::slice::from_raw_parts_mut(ptr, len) };
+        frame.fill(self.fill as u8);
+        frame[0] = Command::DCRamWrite as u8;
+        for (code, c) in frame[1..].iter_mut().rev().zip(text) {
+            *code = c.into() as u8;
+        }
+        self.write_buf_dma(spi, buf).await
+    }
+
+    /// Like [display_dma()](Self::display_dma()), with characters mapped by the [CharMapper](crate::CharMapper)
+    pub async fn display_str_dma<S, B>(
+        &mut self,
+        spi: &mut S,
+        buf: B,
+        text: &str,
+    ) -> (B, Result<(), Error>)
+    where
+        S: DmaWrite,
+        B: ReadBuffer<Word = u8> + WriteBuffer<Word = u8>,
+    {
+        let mapper = self.mapper;
+        self.display_dma(spi, buf, text.chars().map(|c| mapper.map(c)))
+            .await
+    }
+}
@@ -0,0 +1,129 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::interface::Interface;
+use super::{Error, Lights, HCS12SS59T};
+
+/// Brightness levels between `from` (exclusive) and `to` (inclusive)
+fn levels(from: u8, to: u8) -> impl Iterator<Item = u8> {
+    let up = to > from;
+    (1..=from.abs_diff(to)).map(move |i| if up { from + i } else { from - i })
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Ramp the brightness to `brightness` in steps of one over `duration_ms`
+    ///
+    /// Blocks using the delay of the driver. Fading to `0` blanks the display like [brightness()](Self::brightness())
+    /// and keeps the previous brightness. While the lights are off the brightness is set without a ramp.
+    pub fn fade_to(&mut self, brightness: u8, duration_ms: u32) -> Result<(), Error> {
+        let step_ms = self.fade_step_ms(brightness, duration_ms)?;
+        if self.fade_is_jump(brightness) {
+            return self.set_duty(brightness);
+        }
+        let previous = self.brightness;
+        for level in levels(self.brightness, brightness) {
+            self.delay.delay_ms(step_ms);
+            self.set_duty(level)?;
+        }
+        self.keep_after_fade_out(brightness, previous);
+        Ok(())
+    }
+
+    /// Fade from the lowest brightness up to the brightness before [fade_out()](Self::fade_out())
+    pub fn fade_in(&mut self, duration_ms: u32) -> Result<(), Error> {
+        let target = self.brightness;
+        self.set_duty(1)?;
+        self.fade_to(target, duration_ms)
+    }
+
+    /// Fade to off, the previous brightness is kept for [fade_in()](Self::fade_in())
+    pub fn fade_out(&mut self, duration_ms: u32) -> Result<(), Error> {
+        self.fade_to(0, duration_ms)
+    }
+
+    /// Ramp the brightness like [fade_to()](Self::fade_to()), waiting with an async delay
+    #[cfg(feature = "async")]
+    pub async fn fade_to_async<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        brightness: u8,
+        duration_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        let step_ms = self.fade_step_ms(brightness, duration_ms)?;
+        if self.fade_is_jump(brightness) {
+            return self.set_duty(brightness);
+        }
+        let previous = self.brightness;
+        for level in levels(self.brightness, brightness) {
+            delay.delay_ms(step_ms).await;
+            self.set_duty(level)?;
+        }
+        self.keep_after_fade_out(brightness, previous);
+        Ok(())
+    }
+
+    /// Async version of [fade_in()](Self::fade_in())
+    #[cfg(feature = "async")]
+    pub async fn fade_in_async<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        duration_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        let target = self.brightness;
+        self.set_duty(1)?;
+        self.fade_to_async(target, duration_ms, delay).await
+    }
+
+    /// Async version of [fade_out()](Self::fade_out())
+    #[cfg(feature = "async")]
+    pub async fn fade_out_async<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        duration_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        self.fade_to_async(0, duration_ms, delay).await
+    }
+
+    /// Set the brightness like [brightness()](Self::brightness()), a ramp waits with an async delay
+    #[cfg(feature = "async")]
+    pub async fn brightness_async<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        brightness: u8,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        if self.brightness_ramp_ms > 0 {
+            return self
+                .fade_to_async(brightness, self.brightness_ramp_ms, delay)
+                .await;
+        }
+        self.set_duty(brightness)
+    }
+
+    /// Wether the target is set without steps: it is already reached, or the lights are off
+    /// and the display has to be lit again like with [set_duty()](Self::set_duty())
+    fn fade_is_jump(&self, brightness: u8) -> bool {
+        brightness == self.brightness || self.lights == Lights::Off
+    }
+
+    /// Fading to `0` keeps the brightness from before for [wake()](Self::wake()), like `set_duty(0)`
+    fn keep_after_fade_out(&mut self, brightness: u8, previous: u8) {
+        if brightness == 0 {
+            self.brightness = previous;
+        }
+    }
+
+    /// Validates the target brightness and returns the time per step
+    fn fade_step_ms(&self, brightness: u8, duration_ms: u32) -> Result<u32, Error> {
+        if brightness > 15 {
+            return Err(Error::InvalidInput);
+        }
+        let steps = self.brightness.abs_diff(brightness).max(1);
+        Ok(duration_ms / steps as u32)
+    }
+}
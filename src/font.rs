@@ -1,4 +1,9 @@
+use super::CGRAM_SLOTS;
+
 /// HCS-12SS59T Font Table
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum FontTable {
     /// Character `@`
@@ -199,6 +204,118 @@ pub enum FontTable {
     RamF = 0x0F,
 }
 
+impl FontTable {
+    /// All variants ordered by their code, the CGRAM slots first
+    pub const ALL: [FontTable; 80] = {
+        use FontTable::*;
+        [
+            Ram0,
+            Ram1,
+            Ram2,
+            Ram3,
+            Ram4,
+            Ram5,
+            Ram6,
+            Ram7,
+            Ram8,
+            Ram9,
+            RamA,
+            RamB,
+            RamC,
+            RamD,
+            RamE,
+            RamF,
+            ChatAt,
+            CharA,
+            CharB,
+            CharC,
+            CharD,
+            CharE,
+            CharF,
+            CharG,
+            CharH,
+            CharI,
+            CharJ,
+            CharK,
+            CharL,
+            CharM,
+            CharN,
+            CharO,
+            CharP,
+            CharQ,
+            CharR,
+            CharS,
+            CharT,
+            CharU,
+            CharV,
+            CharW,
+            CharX,
+            CharY,
+            CharZ,
+            CharSqBOpen,
+            CharBackslash,
+            CharSqBClose,
+            CharCaret,
+            CharUnderscore,
+            CharSpace,
+            CharExcMrk,
+            CharQuoteMrk,
+            CharHash,
+            CharDollar,
+            CharPercent,
+            CharAmp,
+            CharApost,
+            CharRBOpen,
+            CharRBClose,
+            CharAsterisk,
+            CharPlus,
+            CharComma,
+            CharMinus,
+            CharPeriod,
+            CharSlash,
+            CharZero,
+            CharOne,
+            CharTwo,
+            CharThree,
+            CharFour,
+            CharFive,
+            CharSix,
+            CharSeven,
+            CharEight,
+            CharNine,
+            CharColon,
+            CharSColon,
+            CharLess,
+            CharEqual,
+            CharLarger,
+            CharQestMrk,
+        ]
+    };
+
+    /// Converts a [char], `None` if it is not in the font table
+    ///
+    /// Lowercase letters are converted to uppercase like the conversion from [char],
+    /// which replaces unavailable characters by [?](FontTable::CharQestMrk) instead.
+    pub fn try_from_char(c: char) -> Option<Self> {
+        if !c.is_ascii() {
+            return None;
+        }
+        try_ascii_to_font_code(c as u8).map(|code| code.try_into().unwrap())
+    }
+
+    /// Iterate over the characters of the font ROM, without the CGRAM slots
+    pub fn characters() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter().skip(CGRAM_SLOTS)
+    }
+
+    /// Converts an ASCII byte, same as the conversion from [char]
+    ///
+    /// Non-ASCII bytes are converted to [?](FontTable::CharQestMrk).
+    pub fn from_ascii(byte: u8) -> Self {
+        ascii_to_font_code(byte).try_into().unwrap()
+    }
+}
+
 impl From<char> for FontTable {
     /// Converts a [char] to a [FontTable] variant
     ///
@@ -207,6 +324,26 @@ impl From<char> for FontTable {
         char_to_font_code(value).try_into().unwrap()
     }
 }
+impl From<FontTable> for char {
+    /// Converts a [FontTable] variant back to the [char] it shows
+    ///
+    /// CGRAM slots have no fixed character and are converted to [REPLACEMENT_CHARACTER](char::REPLACEMENT_CHARACTER).
+    fn from(value: FontTable) -> Self {
+        match value as u8 {
+            code @ 0x10..=0x2F => (code + 48) as char,
+            code @ 0x30..=0x4F => (code - 16) as char,
+            _ => char::REPLACEMENT_CHARACTER,
+        }
+    }
+}
+
+impl core::fmt::Display for FontTable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+        f.write_char((*self).into())
+    }
+}
+
 impl TryFrom<u8> for FontTable {
     type Error = ();
 
@@ -215,20 +352,76 @@ impl TryFrom<u8> for FontTable {
         if value > 0x4F {
             Err(())
         } else {
-            unsafe { Ok(core::mem::transmute(value)) }
+            unsafe { Ok(core::mem::transmute::<u8, FontTable>(value)) }
         }
     }
 }
 
+/// Mapping of characters to font codes
+///
+/// Consulted by the `display_str()` methods, set it with [Builder::char_mapper()](super::builder::Builder::char_mapper())
+/// or [HCS12SS59T::set_char_mapper()](super::HCS12SS59T::set_char_mapper()).
+/// Mappers can fall back to [DefaultMapper] for the characters they don't handle:
+///
+/// ``` text
+/// fn my_mapper(c: char) -> FontTable {
+///     match c {
+///         '°' => FontTable::Ram0,
+///         c if c.is_ascii() => DefaultMapper.map(c),
+///         _ => FontTable::CharSpace,
+///     }
+/// }
+/// let vfd = HCS12SS59T::builder().char_mapper(&my_mapper).build(spi, n_reset, delay, cs);
+/// ```
+pub trait CharMapper {
+    /// Font code to display for `c`
+    fn map(&self, c: char) -> FontTable;
+}
+
+/// The built-in mapping, same as converting the character into a [FontTable]
+pub struct DefaultMapper;
+
+impl CharMapper for DefaultMapper {
+    fn map(&self, c: char) -> FontTable {
+        c.into()
+    }
+}
+
+impl<F: Fn(char) -> FontTable> CharMapper for F {
+    fn map(&self, c: char) -> FontTable {
+        self(c)
+    }
+}
+
 pub(crate) fn char_to_font_code(c: char) -> u8 {
     if !c.is_ascii() {
         return 79;
     }
+    ascii_to_font_code(c as u8)
+}
+
+fn ascii_to_font_code(c: u8) -> u8 {
+    try_ascii_to_font_code(c).unwrap_or(79)
+}
+
+fn try_ascii_to_font_code(c: u8) -> Option<u8> {
     match c {
-        '@'..='_' => c as u8 - 48,
-        ' '..='/' => c as u8 + 16,
-        'a'..='z' => c as u8 - 80,
-        '0'..='?' => c as u8 + 16,
-        _ => 79,
+        b'@'..=b'_' => Some(c - 48),
+        b' '..=b'/' => Some(c + 16),
+        b'a'..=b'z' => Some(c - 80),
+        b'0'..=b'?' => Some(c + 16),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_is_ordered_by_code() {
+        for (code, c) in FontTable::ALL.into_iter().enumerate() {
+            assert_eq!(c as usize, code);
+        }
     }
 }
@@ -0,0 +1,284 @@
+//! Number formatting without `core::fmt`
+//!
+//! Used by [display_int()](super::HCS12SS59T::display_int()) and friends, the options are set with [IntFormat]
+//! [FixedFormat] and [RadixFormat].
+
+use super::CharMapper;
+
+/// Horizontal position of text on the display
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alignment {
+    /// Text starts at the leftmost digit
+    Left,
+    /// Text ends at the rightmost digit
+    #[default]
+    Right,
+    /// Text is centered, odd padding puts the extra digit on the right
+    Center,
+}
+
+impl Alignment {
+    /// Number of digits left of text with `len` characters on a display with `width` digits
+    pub(crate) fn padding(self, len: usize, width: usize) -> usize {
+        let space = width.saturating_sub(len);
+        match self {
+            Alignment::Left => 0,
+            Alignment::Right => space,
+            Alignment::Center => space / 2,
+        }
+    }
+}
+
+/// Formatting options for integers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntFormat {
+    /// Minimum number of characters, shorter numbers are padded on the left
+    pub width: u8,
+    /// Pad with zeros after the sign instead of spaces before it
+    pub zero_pad: bool,
+    /// Show `+` for positive numbers
+    pub sign: bool,
+    /// Separate groups of three digits, e.g. `1 234 567` with `' '`
+    ///
+    /// A `,` can be merged into the preceding digit with the [decimal point merging](crate::framebuffer::BufferedHCS12SS59T::set_decimal_point())
+    /// of the buffered driver.
+    pub group_separator: Option<char>,
+    /// Position of the number on the display
+    pub align: Alignment,
+}
+
+impl IntFormat {
+    /// Pad with zeros to `width` characters
+    pub const fn zero_padded(width: u8) -> Self {
+        Self {
+            width,
+            zero_pad: true,
+            sign: false,
+            group_separator: None,
+            align: Alignment::Right,
+        }
+    }
+}
+
+/// Text of one formatted value, at most 32 bytes
+pub struct Formatted {
+    buf: [u8; Self::CAPACITY],
+    len: usize,
+}
+
+impl Formatted {
+    const CAPACITY: usize = 32;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            buf: [0; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Appends a character, characters beyond the capacity are dropped
+    pub(crate) fn push(&mut self, c: char) {
+        if self.len + c.len_utf8() <= Self::CAPACITY {
+            self.len += c.encode_utf8(&mut self.buf[self.len..]).len();
+        }
+    }
+
+    /// Appends a string, see [push()](Self::push())
+    pub(crate) fn push_str(&mut self, s: &str) {
+        s.chars().for_each(|c| self.push(c));
+    }
+
+    /// The formatted text
+    pub fn as_str(&self) -> &str {
+        // Only complete characters are pushed
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl core::fmt::Write for Formatted {
+    /// Appends the string, truncating it at the capacity
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+/// [core::fmt::Write] target filling display cells from the left
+///
+/// Cells are in DCRAM order, i.e. the last cell is the leftmost digit.
+/// Formatting is aborted with an error once all cells are filled.
+pub(crate) struct CellWriter<'a> {
+    pub(crate) cells: &'a mut [u8],
+    pub(crate) pos: usize,
+    pub(crate) mapper: &'static dyn CharMapper,
+}
+
+impl core::fmt::Write for CellWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            let len = self.cells.len();
+            if self.pos >= len {
+                return Err(core::fmt::Error);
+            }
+            self.cells[len - 1 - self.pos] = self.mapper.map(c) as u8;
+            self.pos += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Formatting options for fixed-point numbers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedFormat {
+    /// Options for sign, padding and alignment, the width includes the separator
+    pub int: IntFormat,
+    /// Character between the integer and fractional part
+    ///
+    /// The built-in font renders `.` blank, use the [decimal point merging](crate::framebuffer::BufferedHCS12SS59T::set_decimal_point())
+    /// of the buffered driver or another character (e.g. `-` or a custom character via a [CharMapper]).
+    pub separator: char,
+}
+
+impl Default for FixedFormat {
+    fn default() -> Self {
+        Self {
+            int: IntFormat::default(),
+            separator: '.',
+        }
+    }
+}
+
+/// Formatting options for hexadecimal and binary numbers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadixFormat {
+    /// Minimum number of digits, shorter numbers are padded with zeros
+    pub digits: u8,
+    /// Prepend `0x` or `0b`
+    pub prefix: bool,
+    /// Insert the separator every `group` digits (counted from the right), `0` disables grouping
+    pub group: u8,
+    /// Character between groups
+    pub separator: char,
+    /// Position of the number on the display
+    pub align: Alignment,
+}
+
+impl Default for RadixFormat {
+    fn default() -> Self {
+        Self {
+            digits: 1,
+            prefix: false,
+            group: 0,
+            separator: ' ',
+            align: Alignment::Right,
+        }
+    }
+}
+
+/// Formats a number with `bits` bits per digit, i.e. 4 for hexadecimal and 1 for binary
+pub(crate) fn format_radix(value: u32, bits: u32, prefix: &str, format: RadixFormat) -> Formatted {
+    let mask = (1 << bits) - 1;
+    let significant = (u32::BITS - value.leading_zeros()).div_ceil(bits).max(1) as usize;
+    let max_digits = u32::BITS.div_ceil(bits) as usize;
+    let n_digits = significant.max(format.digits as usize).min(max_digits);
+
+    let mut out = Formatted::new();
+    if format.prefix {
+        out.push_str(prefix);
+    }
+    for i in (0..n_digits).rev() {
+        let digit = (value >> (i as u32 * bits)) & mask;
+        out.push(
+            char::from_digit(digit, 1 << bits)
+                .unwrap_or('?')
+                .to_ascii_uppercase(),
+        );
+        if format.group > 0 && i > 0 && i % format.group as usize == 0 {
+            out.push(format.separator);
+        }
+    }
+    out
+}
+
+/// Scales a float to a fixed-point value with `decimals` places, rounding half away from zero
+///
+/// Values out of range saturate, NaN results in zero.
+pub(crate) fn f32_to_fixed(value: f32, decimals: u8) -> i32 {
+    let scale = (0..decimals.min(MAX_DECIMALS)).fold(1.0_f32, |acc, _| acc * 10.0);
+    let scaled = value * scale;
+    if scaled < 0.0 {
+        (scaled - 0.5) as i32
+    } else {
+        (scaled + 0.5) as i32
+    }
+}
+
+/// Largest supported number of decimal places, `10^9` still fits into an `u32`
+pub(crate) const MAX_DECIMALS: u8 = 9;
+
+/// Formats the magnitude and sign of an integer
+pub(crate) fn format_int(negative: bool, magnitude: u32, format: IntFormat) -> Formatted {
+    format_fixed(negative, magnitude, 0, '.', format)
+}
+
+/// Formats the magnitude and sign of a fixed-point number with `decimals` places
+pub(crate) fn format_fixed(
+    negative: bool,
+    magnitude: u32,
+    decimals: u8,
+    separator: char,
+    format: IntFormat,
+) -> Formatted {
+    let decimals = decimals.min(MAX_DECIMALS) as usize;
+    let mut digits = [0_u8; 10];
+    let mut n_digits = 0;
+    let mut rest = magnitude;
+    while n_digits <= decimals || rest != 0 {
+        digits[n_digits] = b'0' + (rest % 10) as u8;
+        n_digits += 1;
+        rest /= 10;
+    }
+
+    let sign = match (negative, format.sign) {
+        (true, _) => Some('-'),
+        (false, true) => Some('+'),
+        (false, false) => None,
+    };
+    let groups = match format.group_separator {
+        Some(_) => (n_digits - decimals - 1) / 3,
+        None => 0,
+    };
+    let len = n_digits + sign.is_some() as usize + (decimals > 0) as usize + groups;
+    let padding = (format.width as usize).saturating_sub(len);
+
+    let mut out = Formatted::new();
+    if !format.zero_pad {
+        (0..padding).for_each(|_| out.push(' '));
+    }
+    if let Some(sign) = sign {
+        out.push(sign);
+    }
+    if format.zero_pad {
+        (0..padding).for_each(|_| out.push('0'));
+    }
+    for (i, d) in digits[..n_digits].iter().enumerate().rev() {
+        out.push(*d as char);
+        if i == decimals && decimals > 0 {
+            out.push(separator);
+        }
+        if let Some(group_separator) = format.group_separator {
+            if i > decimals && (i - decimals).is_multiple_of(3) {
+                out.push(group_separator);
+            }
+        }
+    }
+    out
+}
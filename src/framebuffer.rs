@@ -0,0 +1,555 @@
+use core::iter::Peekable;
+use core::str::Chars;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::format::{self, Alignment, FixedFormat, IntFormat, RadixFormat};
+use super::glyph::Glyph;
+use super::glyphs;
+use super::interface::Interface;
+use super::{DisplayBuffer, Error, FontTable, CGRAM_SLOTS, HCS12SS59T, NUM_DIGITS};
+
+/// Buffered HCS12SS59T
+///
+/// Keeps a shadow of the DCRAM and CGRAM contents. Characters and patterns can be changed freely
+/// without touching the bus, only the changed (dirty) bytes are transmitted on [flush()](Self::flush()).
+pub struct BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, const N: usize = NUM_DIGITS> {
+    driver: HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    dcram: [u8; N],
+    cgram: [[u8; 2]; CGRAM_SLOTS],
+    dcram_dirty: u16,
+    cgram_dirty: u16,
+    cgram_used: u16,
+    cgram_user: u16,
+    cgram_cached: u16,
+    lowercase: bool,
+    decimal_point: Option<Glyph>,
+    cursor: usize,
+    shift: usize,
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Wraps a HCS12SS59T into a buffered driver
+    ///
+    /// The buffer starts out filled with the fill character and completely dirty, so the first
+    /// [flush()](Self::flush()) clears the display like [clear()](Self::clear()).
+    pub fn new(driver: HCS12SS59T<IF, RstPin, VdonPin, Delay, N>) -> Self {
+        Self {
+            dcram: [driver.fill as u8; N],
+            driver,
+            cgram: [[0; 2]; CGRAM_SLOTS],
+            dcram_dirty: ((1_u32 << N) - 1) as u16,
+            cgram_dirty: 0,
+            cgram_used: 0,
+            cgram_user: 0,
+            cgram_cached: 0,
+            lowercase: false,
+            decimal_point: None,
+            cursor: 0,
+            shift: 0,
+        }
+    }
+
+    /// Returns the wrapped driver
+    ///
+    /// Pending changes are discarded, call [flush()](Self::flush()) before if necessary.
+    pub fn release(self) -> HCS12SS59T<IF, RstPin, VdonPin, Delay, N> {
+        self.driver
+    }
+
+    /// Access the wrapped driver, e.g. to change the brightness
+    pub fn driver(&mut self) -> &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N> {
+        &mut self.driver
+    }
+
+    /// Set a single character in the buffer
+    ///
+    /// Addresses are the same as for [HCS12SS59T::set_char()], `0` is the rightmost digit.
+    pub fn set_char<C: Into<FontTable>>(&mut self, addr: u8, char: C) -> Result<(), Error> {
+        let addr = addr as usize;
+        if addr >= N {
+            return Err(Error::InvalidInput);
+        }
+        let code = char.into() as u8;
+        if self.dcram[addr] != code {
+            self.dcram[addr] = code;
+            self.dcram_dirty |= 1 << addr;
+        }
+        Ok(())
+    }
+
+    /// Character at `addr` in the buffer, `None` if out of range
+    ///
+    /// The controller is write-only, the buffer keeps the content instead. Addresses are the same as for
+    /// [set_char()](Self::set_char()), `0` is the rightmost digit. Changes show up before they are flushed.
+    pub fn get_char(&self, addr: u8) -> Option<FontTable> {
+        let code = *self.dcram.get(addr as usize)?;
+        FontTable::try_from(code).ok()
+    }
+
+    /// Content of the buffer, leftmost digit first
+    ///
+    /// E.g. to save the content and show it again later with [display_buffer()](Self::display_buffer()).
+    pub fn contents(&self) -> DisplayBuffer<N> {
+        DisplayBuffer(core::array::from_fn(|pos| {
+            FontTable::try_from(self.dcram[N - 1 - pos]).unwrap_or(FontTable::CharSpace)
+        }))
+    }
+
+    /// Write text to the buffer
+    ///
+    /// Behaves like [HCS12SS59T::display()], the text starts at the leftmost digit and is truncated to fit the display.
+    pub fn display<T>(&mut self, text: T)
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        let mut text = text.into_iter();
+        for addr in (0..N).rev() {
+            let c = text.next().map_or(self.driver.fill, Into::into);
+            // Address is always in range
+            let _ = self.set_char(addr as u8, c);
+        }
+    }
+
+    /// Copy a composed [DisplayBuffer] into the buffer
+    pub fn display_buffer(&mut self, buffer: &DisplayBuffer<N>) {
+        self.display(*buffer);
+    }
+
+    /// Fill the whole buffer with blanks
+    ///
+    /// Also moves the cursor back to the leftmost digit.
+    pub fn clear(&mut self) {
+        self.display(core::iter::empty::<FontTable>());
+        self.cursor = 0;
+    }
+
+    /// Move the cursor for [write_str()](Self::write_str()), `0` is the leftmost digit
+    pub fn set_cursor(&mut self, pos: usize) {
+        self.cursor = pos;
+    }
+
+    /// Move the cursor back to the leftmost digit
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Write text at the cursor position and advance the cursor
+    ///
+    /// Unlike [display()](Self::display()) the rest of the buffer is left untouched,
+    /// text beyond the last digit is dropped.
+    pub fn write_str(&mut self, text: &str) {
+        let mut text = text.chars().peekable();
+        while self.cursor < N {
+            let Some(c) = self.next_cell(&mut text) else {
+                break;
+            };
+            // Address is always in range
+            let _ = self.set_char((N - 1 - self.cursor) as u8, c);
+            self.cursor += 1;
+        }
+    }
+
+    /// Write a string to the buffer
+    ///
+    /// Like [display()](Self::display()), but characters are mapped with the [CharMapper](crate::CharMapper) of the driver
+    /// and lowercase letters are rendered if enabled with [set_lowercase()](Self::set_lowercase()).
+    pub fn display_str(&mut self, text: &str) {
+        let mut text = text.chars().peekable();
+        for addr in (0..N).rev() {
+            let c = self.next_cell(&mut text).unwrap_or(self.driver.fill);
+            // Address is always in range
+            let _ = self.set_char(addr as u8, c);
+        }
+    }
+
+    /// Write ASCII bytes to the buffer, see [HCS12SS59T::display_ascii()]
+    pub fn display_ascii(&mut self, text: &[u8]) {
+        self.display(text.iter().map(|b| FontTable::from_ascii(*b)));
+    }
+
+    /// Write formatted text to the buffer, see [HCS12SS59T::display_fmt()]
+    ///
+    /// Formatted into a 32 byte buffer first, then written like with [display_str()](Self::display_str()).
+    pub fn display_fmt(&mut self, args: core::fmt::Arguments<'_>) {
+        let mut text = format::Formatted::new();
+        // Writing to Formatted never fails, it truncates
+        let _ = core::fmt::write(&mut text, args);
+        self.display_str(text.as_str());
+    }
+
+    /// Write a signed integer to the buffer, see [HCS12SS59T::display_int()]
+    pub fn display_int(&mut self, value: i32, format: IntFormat) {
+        let text = format::format_int(value < 0, value.unsigned_abs(), format);
+        self.display_aligned(text.as_str(), format.align, self.driver.fill);
+    }
+
+    /// Write an unsigned integer to the buffer, see [HCS12SS59T::display_uint()]
+    pub fn display_uint(&mut self, value: u32, format: IntFormat) {
+        let text = format::format_int(false, value, format);
+        self.display_aligned(text.as_str(), format.align, self.driver.fill);
+    }
+
+    /// Write a fixed-point number to the buffer, see [HCS12SS59T::display_fixed()]
+    ///
+    /// With [set_decimal_point()](Self::set_decimal_point()) the separator `.` is merged into the last integer digit.
+    pub fn display_fixed(&mut self, value: i32, decimals: u8, format: FixedFormat) {
+        let text = format::format_fixed(
+            value < 0,
+            value.unsigned_abs(),
+            decimals,
+            format.separator,
+            format.int,
+        );
+        self.display_aligned(text.as_str(), format.int.align, self.driver.fill);
+    }
+
+    /// Write a float rounded to `decimals` places to the buffer, see [HCS12SS59T::display_f32()]
+    pub fn display_f32(&mut self, value: f32, decimals: u8, format: FixedFormat) {
+        self.display_fixed(format::f32_to_fixed(value, decimals), decimals, format);
+    }
+
+    /// Write a number in hexadecimal to the buffer, see [HCS12SS59T::display_hex()]
+    pub fn display_hex(&mut self, value: u32, format: RadixFormat) {
+        let text = format::format_radix(value, 4, "0x", format);
+        self.display_aligned(text.as_str(), format.align, self.driver.fill);
+    }
+
+    /// Write a number in binary to the buffer, see [HCS12SS59T::display_bin()]
+    pub fn display_bin(&mut self, value: u16, format: RadixFormat) {
+        let text = format::format_radix(value.into(), 1, "0b", format);
+        self.display_aligned(text.as_str(), format.align, self.driver.fill);
+    }
+
+    /// Write a string with the given alignment to the buffer, see [HCS12SS59T::display_aligned()]
+    ///
+    /// The text is rendered like with [display_str()](Self::display_str()), the cursor is left untouched.
+    pub fn display_aligned<C: Into<FontTable>>(&mut self, text: &str, align: Alignment, fill: C) {
+        let padding = align.padding(self.cell_count(text), N);
+        let cursor = self.cursor;
+        self.display(core::iter::repeat_n(fill.into(), N));
+        self.cursor = padding;
+        self.write_str(text);
+        self.cursor = cursor;
+    }
+
+    /// Character padding shorter text, set with the builder's [fill()](crate::builder::Builder::fill())
+    pub(crate) fn fill(&self) -> FontTable {
+        self.driver.fill
+    }
+
+    /// Number of cells the text occupies, taking merged decimal points into account
+    pub(crate) fn cell_count(&self, text: &str) -> usize {
+        let mut cells = 0;
+        let mut mergeable = false;
+        for c in text.chars() {
+            if self.decimal_point.is_some() && mergeable && matches!(c, '.' | ',') {
+                mergeable = false;
+                continue;
+            }
+            mergeable =
+                glyphs::digit(c).is_some() || (self.lowercase && glyphs::lowercase(c).is_some());
+            cells += 1;
+        }
+        cells
+    }
+
+    /// Render lowercase letters with custom characters
+    ///
+    /// When enabled, [display_str()](Self::display_str()) and [write_str()](Self::write_str()) upload the
+    /// [lowercase](glyphs::lowercase()) shapes to CGRAM slots on demand, instead of using the uppercase font.
+    /// Slots set with [set_cgram_pattern()](Self::set_cgram_pattern()) are never touched.
+    /// A slot is only reused when no digit shows its letter anymore,
+    /// if all slots are taken the uppercase character is displayed.
+    pub fn set_lowercase(&mut self, enable: bool) {
+        self.lowercase = enable;
+    }
+
+    /// Merge decimal points into the preceding digit
+    ///
+    /// When set, [display_str()](Self::display_str()) and [write_str()](Self::write_str()) draw a `.` or `,`
+    /// following a digit into the cell of that digit, using a custom character made of the [digit](glyphs::digit())
+    /// and the `marker` segments, e.g. [glyphs::DECIMAL_POINT]. `12.5V` then takes four digits instead of five.
+    /// Like lowercase letters, the custom characters are cached in free CGRAM slots.
+    ///
+    /// `None` disables merging, the font renders `.` and `,` blank.
+    pub fn set_decimal_point(&mut self, marker: Option<Glyph>) {
+        self.decimal_point = marker;
+    }
+
+    /// Rotate the content by `shift` digits to the right when flushing, characters leaving on the right wrap around
+    ///
+    /// The buffer itself is not changed, so the content can be updated as usual. Used by the
+    /// [Screensaver](crate::widget::Screensaver) to even out the wear of the display.
+    pub fn set_shift(&mut self, shift: usize) {
+        let shift = shift % N.max(1);
+        if shift != self.shift {
+            self.shift = shift;
+            self.dcram_dirty = ((1_u32 << N) - 1) as u16;
+        }
+    }
+
+    /// Font code for the next cell of the text, uploading custom characters if necessary
+    pub(crate) fn next_cell(&mut self, text: &mut Peekable<Chars<'_>>) -> Option<FontTable> {
+        let c = text.next()?;
+        let glyph = glyphs::lowercase(c).filter(|_| self.lowercase);
+        if let Some(marker) = self.decimal_point {
+            if matches!(c, '.' | ',') {
+                return Some(
+                    self.cached_glyph(marker)
+                        .unwrap_or_else(|| self.driver.mapper.map(c)),
+                );
+            }
+            let merged = match text.peek() {
+                Some('.' | ',') => glyph.or_else(|| glyphs::digit(c)),
+                _ => None,
+            };
+            if let Some(code) = merged.and_then(|base| self.cached_glyph(base.combine(marker))) {
+                text.next();
+                return Some(code);
+            }
+        }
+        let code = glyph.and_then(|glyph| self.cached_glyph(glyph));
+        Some(code.unwrap_or_else(|| self.driver.mapper.map(c)))
+    }
+
+    /// Font code of a cached custom character, uploads it to a free CGRAM slot if necessary
+    ///
+    /// Returns `None` if all slots are in use.
+    fn cached_glyph(&mut self, glyph: Glyph) -> Option<FontTable> {
+        let pattern = glyph.pattern();
+        let slot = (0..CGRAM_SLOTS)
+            .find(|slot| self.cgram_cached & 1 << slot != 0 && self.cgram[*slot] == pattern);
+        let slot = match slot {
+            Some(slot) => slot,
+            None => {
+                let displayed = self
+                    .dcram
+                    .iter()
+                    .filter(|code| (**code as usize) < CGRAM_SLOTS)
+                    .fold(0_u16, |acc, code| acc | 1 << code);
+                let free = !(self.cgram_user | displayed);
+                if free == 0 {
+                    return None;
+                }
+                // Prefer slots which never held a pattern, to keep the cache warm
+                let slot = if free & !self.cgram_used != 0 {
+                    (free & !self.cgram_used).trailing_zeros()
+                } else {
+                    free.trailing_zeros()
+                } as usize;
+                self.store_cgram(slot, pattern);
+                self.cgram_cached |= 1 << slot;
+                slot
+            }
+        };
+        // Slot is always a valid CGRAM address
+        Some(FontTable::try_from(slot as u8).unwrap())
+    }
+
+    fn store_cgram(&mut self, slot: usize, pattern: [u8; 2]) {
+        if self.cgram[slot] != pattern {
+            self.cgram[slot] = pattern;
+            self.cgram_dirty |= 1 << slot;
+        }
+        self.cgram_used |= 1 << slot;
+    }
+
+    /// Set a character pattern in the CGRAM buffer
+    ///
+    /// See [HCS12SS59T::set_cgram_pattern()] for the pattern layout.
+    pub fn set_cgram_pattern<P: Into<[u8; 2]>>(
+        &mut self,
+        addr: FontTable,
+        pattern: P,
+    ) -> Result<(), Error> {
+        let pattern = pattern.into();
+        let slot = addr as usize;
+        if slot >= CGRAM_SLOTS {
+            return Err(Error::InvalidInput);
+        }
+        self.store_cgram(slot, pattern);
+        self.cgram_user |= 1 << slot;
+        self.cgram_cached &= !(1 << slot);
+        Ok(())
+    }
+
+    /// Set several CGRAM patterns in the buffer, see [HCS12SS59T::load_glyphs()]
+    pub fn load_glyphs(&mut self, glyphs: &[(FontTable, Glyph)]) -> Result<(), Error> {
+        glyphs
+            .iter()
+            .try_for_each(|(addr, glyph)| self.set_cgram_pattern(*addr, *glyph))
+    }
+
+    /// Transmit all changes to the display
+    ///
+    /// CGRAM patterns are written first, so new custom characters appear together with the text using them.
+    /// Consecutive dirty characters are sent in one command.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        while self.cgram_dirty != 0 {
+            let slot = self.cgram_dirty.trailing_zeros() as u8;
+            // Slot is always a valid CGRAM address
+            let addr = FontTable::try_from(slot).unwrap();
+            self.driver
+                .set_cgram_pattern(addr, self.cgram[slot as usize])?;
+            self.cgram_dirty &= !(1 << slot);
+        }
+
+        if self.shift != 0 && self.dcram_dirty != 0 {
+            // Dirty runs can wrap around, the whole rotated buffer is sent instead
+            let mut rotated = [0_u8; N];
+            for (addr, code) in self.dcram.iter().enumerate() {
+                rotated[(addr + N - self.shift) % N] = *code;
+            }
+            self.driver.write_dcram(0, &rotated)?;
+            self.dcram_dirty = 0;
+        }
+        while self.dcram_dirty != 0 {
+            let start = self.dcram_dirty.trailing_zeros() as usize;
+            let len = (self.dcram_dirty >> start).trailing_ones() as usize;
+            self.driver
+                .write_dcram(start as u8, &self.dcram[start..start + len])?;
+            self.dcram_dirty &= !((((1_u32 << len) - 1) << start) as u16);
+        }
+        Ok(())
+    }
+
+    /// Show a centered message for `duration_ms`, then restore the previous content
+    ///
+    /// Blocks with the delay of the driver. Everything in the buffer, including custom characters the message
+    /// needed CGRAM slots for, is restored afterwards. Animations are not advanced meanwhile,
+    /// so they continue where they were interrupted.
+    ///
+    /// Changes which were not flushed yet are sent as well: pending CGRAM patterns with the message,
+    /// pending characters with the restored content. If showing the message fails, the buffer is restored
+    /// before the error is returned, the next [flush()](Self::flush()) brings the display up to date.
+    pub fn show_toast(&mut self, text: &str, duration_ms: u32) -> Result<(), Error> {
+        let dcram = self.dcram;
+        let cgram = self.cgram;
+        let (used, cached, cursor) = (self.cgram_used, self.cgram_cached, self.cursor);
+
+        self.display_aligned(text, Alignment::Center, self.driver.fill);
+        let shown = self.flush();
+        if shown.is_ok() {
+            self.driver.delay.delay_ms(duration_ms);
+        }
+
+        for (slot, pattern) in cgram.iter().enumerate() {
+            if self.cgram[slot] != *pattern {
+                self.cgram[slot] = *pattern;
+                self.cgram_dirty |= 1 << slot;
+            }
+        }
+        (self.cgram_used, self.cgram_cached, self.cursor) = (used, cached, cursor);
+        for (addr, code) in dcram.iter().enumerate() {
+            if self.dcram[addr] != *code {
+                self.dcram[addr] = *code;
+                self.dcram_dirty |= 1 << addr;
+            }
+        }
+        shown?;
+        self.flush()
+    }
+
+    /// Replay the whole state to the display
+    ///
+    /// Sends the settings (see [HCS12SS59T::reapply_settings()]), all CGRAM patterns that were set and the complete DCRAM.
+    /// Use this after the controller lost its state, e.g. after the supply was cycled or the chip browned out.
+    pub fn reapply(&mut self) -> Result<(), Error> {
+        self.driver.reapply_settings()?;
+        self.cgram_dirty = self.cgram_used;
+        self.dcram_dirty = ((1_u32 << N) - 1) as u16;
+        self.flush()
+    }
+}
+
+/// Double buffered HCS12SS59T
+///
+/// Frames are rendered into the [back()](Self::back()) buffer, possibly over a long time, and shown in one burst
+/// with [swap()](Self::swap()). Partially composed frames never become visible.
+pub struct DoubleBufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, const N: usize = NUM_DIGITS> {
+    driver: HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    front: DisplayBuffer<N>,
+    back: DisplayBuffer<N>,
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize>
+    DoubleBufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Wraps a HCS12SS59T, both buffers start out blank
+    pub fn new(driver: HCS12SS59T<IF, RstPin, VdonPin, Delay, N>) -> Self {
+        Self {
+            driver,
+            front: DisplayBuffer::new_with_digits(),
+            back: DisplayBuffer::new_with_digits(),
+        }
+    }
+
+    /// Returns the wrapped driver
+    pub fn release(self) -> HCS12SS59T<IF, RstPin, VdonPin, Delay, N> {
+        self.driver
+    }
+
+    /// Access the wrapped driver, e.g. to change the brightness
+    pub fn driver(&mut self) -> &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N> {
+        &mut self.driver
+    }
+
+    /// The buffer being rendered into
+    pub fn back(&mut self) -> &mut DisplayBuffer<N> {
+        &mut self.back
+    }
+
+    /// The buffer currently shown
+    pub fn front(&self) -> &DisplayBuffer<N> {
+        &self.front
+    }
+
+    /// Transmit the back buffer in one write and exchange the buffers
+    ///
+    /// Afterwards the back buffer holds the previously shown frame. If the next frame builds on the current
+    /// one, use [swap_copy()](Self::swap_copy()) instead.
+    pub fn swap(&mut self) -> Result<(), Error> {
+        self.driver.display_buffer(&self.back)?;
+        core::mem::swap(&mut self.front, &mut self.back);
+        Ok(())
+    }
+
+    /// Transmit the back buffer in one write, the back buffer keeps its content
+    pub fn swap_copy(&mut self) -> Result<(), Error> {
+        self.driver.display_buffer(&self.back)?;
+        self.front = self.back;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<IF, RstPin, VdonPin, Delay, const N: usize> ufmt::uWrite
+    for BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    type Error = core::convert::Infallible;
+
+    /// Writes at the cursor, see [BufferedHCS12SS59T::write_str()]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        BufferedHCS12SS59T::write_str(self, s);
+        Ok(())
+    }
+}
@@ -0,0 +1,416 @@
+//! Custom character patterns
+//!
+//! [Segments] names the segments of a character cell, [Glyph] builds CGRAM patterns from them:
+//!
+//! ``` text
+//! let arrow = Glyph::builder().center_h().diag_upper_right().diag_lower_right();
+//! vfd.set_cgram_pattern(FontTable::Ram0, arrow)?;
+//! ```
+//!
+//! Segment names and their position (SEGx as in [set_cgram_pattern()](super::HCS12SS59T::set_cgram_pattern())):
+//!
+//! ``` text
+//!   A1(1)     A2(2)
+//!  F  H     I     J  B
+//! (8) (16) (9) (10) (3)
+//!   G1(15)    G2(11)
+//!  E  M     L     K  C
+//! (7) (14) (13) (12) (4)
+//!   D1(6)     D2(5)
+//! ```
+//!
+//! On 14 segment displays SEG2 and SEG5 are not connected, [Segments::A] and [Segments::D]
+//! (and the builder methods) set both halves, so glyphs work on both display types.
+//!
+//! With the `std` feature `render_pattern()` draws patterns as ASCII art, e.g. in test failures or logs.
+//! Each segment is a line of `-`, `|`, `/` or `\`, with all of them set a cell looks like this:
+//!
+//! ``` text
+//!  ------- -------
+//! |\      |      /|
+//! | \     |     / |
+//! |  \    |    /  |
+//! |   \   |   /   |
+//!  ------- -------
+//! |   /   |   \   |
+//! |  /    |    \  |
+//! | /     |     \ |
+//! |/      |      \|
+//!  ------- -------
+//! ```
+
+use core::ops::{BitOr, BitOrAssign};
+
+/// Set of segments of one character cell
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segments(u16);
+
+impl Segments {
+    /// No segment
+    pub const NONE: Self = Self(0);
+    /// Top, left half (SEG1)
+    pub const A1: Self = Self(1 << 0);
+    /// Top, right half (SEG2)
+    pub const A2: Self = Self(1 << 1);
+    /// Right, upper (SEG3)
+    pub const B: Self = Self(1 << 2);
+    /// Right, lower (SEG4)
+    pub const C: Self = Self(1 << 3);
+    /// Bottom, right half (SEG5)
+    pub const D2: Self = Self(1 << 4);
+    /// Bottom, left half (SEG6)
+    pub const D1: Self = Self(1 << 5);
+    /// Left, lower (SEG7)
+    pub const E: Self = Self(1 << 6);
+    /// Left, upper (SEG8)
+    pub const F: Self = Self(1 << 7);
+    /// Center, upper vertical (SEG9)
+    pub const I: Self = Self(1 << 8);
+    /// Diagonal, upper right (SEG10)
+    pub const J: Self = Self(1 << 9);
+    /// Middle, right half (SEG11)
+    pub const G2: Self = Self(1 << 10);
+    /// Diagonal, lower right (SEG12)
+    pub const K: Self = Self(1 << 11);
+    /// Center, lower vertical (SEG13)
+    pub const L: Self = Self(1 << 12);
+    /// Diagonal, lower left (SEG14)
+    pub const M: Self = Self(1 << 13);
+    /// Middle, left half (SEG15)
+    pub const G1: Self = Self(1 << 14);
+    /// Diagonal, upper left (SEG16)
+    pub const H: Self = Self(1 << 15);
+
+    /// Complete top bar
+    pub const A: Self = Self(Self::A1.0 | Self::A2.0);
+    /// Complete bottom bar
+    pub const D: Self = Self(Self::D1.0 | Self::D2.0);
+    /// Complete middle bar
+    pub const G: Self = Self(Self::G1.0 | Self::G2.0);
+    /// All segments
+    pub const ALL: Self = Self(0xFFFF);
+
+    /// Segments from the raw bits, bit 0 is SEG1
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    /// Raw bits, bit 0 is SEG1
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Segments from a CGRAM pattern
+    pub const fn from_pattern(pattern: [u8; 2]) -> Self {
+        Self(u16::from_le_bytes(pattern))
+    }
+
+    /// CGRAM pattern of the segments
+    pub const fn pattern(self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+
+    /// Segments set in `self` or `other`
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Segments of `self` without the ones of `other`
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns wether all segments of `other` are set
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Mirror left and right
+    pub const fn mirror_horizontal(self) -> Self {
+        self.swap(Self::A1, Self::A2)
+            .swap(Self::F, Self::B)
+            .swap(Self::H, Self::J)
+            .swap(Self::G1, Self::G2)
+            .swap(Self::E, Self::C)
+            .swap(Self::M, Self::K)
+            .swap(Self::D1, Self::D2)
+    }
+
+    /// Mirror top and bottom
+    pub const fn mirror_vertical(self) -> Self {
+        self.swap(Self::A1, Self::D1)
+            .swap(Self::A2, Self::D2)
+            .swap(Self::F, Self::E)
+            .swap(Self::B, Self::C)
+            .swap(Self::H, Self::M)
+            .swap(Self::J, Self::K)
+            .swap(Self::I, Self::L)
+    }
+
+    /// Exchange the state of two single segments
+    const fn swap(self, a: Self, b: Self) -> Self {
+        let mut bits = self.0 & !(a.0 | b.0);
+        if self.0 & a.0 != 0 {
+            bits |= b.0;
+        }
+        if self.0 & b.0 != 0 {
+            bits |= a.0;
+        }
+        Self(bits)
+    }
+}
+
+impl BitOr for Segments {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for Segments {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+
+/// Custom character for the CGRAM
+///
+/// Start with [Glyph::builder()] and add segments with the named methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Glyph(Segments);
+
+impl Glyph {
+    /// Glyph without any segment
+    pub const EMPTY: Self = Self(Segments::NONE);
+
+    /// Start building a glyph, same as [Glyph::EMPTY]
+    pub const fn builder() -> Self {
+        Self::EMPTY
+    }
+
+    /// Glyph from a set of segments
+    pub const fn from_segments(segments: Segments) -> Self {
+        Self(segments)
+    }
+
+    /// The segments of the glyph
+    pub const fn segments(self) -> Segments {
+        self.0
+    }
+
+    /// CGRAM pattern of the glyph
+    pub const fn pattern(self) -> [u8; 2] {
+        self.0.pattern()
+    }
+
+    /// Add arbitrary segments
+    pub const fn with(self, segments: Segments) -> Self {
+        Self(self.0.union(segments))
+    }
+
+    /// Add all segments of another glyph
+    pub const fn combine(self, other: Self) -> Self {
+        self.with(other.0)
+    }
+
+    /// Mirror left and right
+    pub const fn mirror_horizontal(self) -> Self {
+        Self(self.0.mirror_horizontal())
+    }
+
+    /// Mirror top and bottom
+    pub const fn mirror_vertical(self) -> Self {
+        Self(self.0.mirror_vertical())
+    }
+
+    /// Top bar ([Segments::A])
+    pub const fn top(self) -> Self {
+        self.with(Segments::A)
+    }
+
+    /// Bottom bar ([Segments::D])
+    pub const fn bottom(self) -> Self {
+        self.with(Segments::D)
+    }
+
+    /// Left upper segment ([Segments::F])
+    pub const fn upper_left(self) -> Self {
+        self.with(Segments::F)
+    }
+
+    /// Right upper segment ([Segments::B])
+    pub const fn upper_right(self) -> Self {
+        self.with(Segments::B)
+    }
+
+    /// Left lower segment ([Segments::E])
+    pub const fn lower_left(self) -> Self {
+        self.with(Segments::E)
+    }
+
+    /// Right lower segment ([Segments::C])
+    pub const fn lower_right(self) -> Self {
+        self.with(Segments::C)
+    }
+
+    /// Left half of the middle bar ([Segments::G1])
+    pub const fn center_left(self) -> Self {
+        self.with(Segments::G1)
+    }
+
+    /// Right half of the middle bar ([Segments::G2])
+    pub const fn center_right(self) -> Self {
+        self.with(Segments::G2)
+    }
+
+    /// Complete middle bar ([Segments::G])
+    pub const fn center_h(self) -> Self {
+        self.with(Segments::G)
+    }
+
+    /// Upper half of the center vertical ([Segments::I])
+    pub const fn center_upper(self) -> Self {
+        self.with(Segments::I)
+    }
+
+    /// Lower half of the center vertical ([Segments::L])
+    pub const fn center_lower(self) -> Self {
+        self.with(Segments::L)
+    }
+
+    /// Complete center vertical ([Segments::I] and [Segments::L])
+    pub const fn center_v(self) -> Self {
+        self.with(Segments::I).with(Segments::L)
+    }
+
+    /// Upper left diagonal ([Segments::H])
+    pub const fn diag_upper_left(self) -> Self {
+        self.with(Segments::H)
+    }
+
+    /// Upper right diagonal ([Segments::J])
+    pub const fn diag_upper_right(self) -> Self {
+        self.with(Segments::J)
+    }
+
+    /// Lower left diagonal ([Segments::M])
+    pub const fn diag_lower_left(self) -> Self {
+        self.with(Segments::M)
+    }
+
+    /// Lower right diagonal ([Segments::K])
+    pub const fn diag_lower_right(self) -> Self {
+        self.with(Segments::K)
+    }
+}
+
+impl From<Segments> for Glyph {
+    fn from(value: Segments) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Glyph> for [u8; 2] {
+    fn from(value: Glyph) -> Self {
+        value.pattern()
+    }
+}
+
+impl From<Segments> for [u8; 2] {
+    fn from(value: Segments) -> Self {
+        value.pattern()
+    }
+}
+
+/// Render a CGRAM pattern as ASCII art in the layout shown in the [module documentation](self)
+///
+/// The result has 11 lines of 17 characters, unset segments are blank.
+#[cfg(feature = "std")]
+pub fn render_pattern(pattern: [u8; 2]) -> std::string::String {
+    const WIDTH: usize = 17;
+    const HEIGHT: usize = 11;
+    let segments = Segments::from_pattern(pattern);
+    let mut grid = [[' '; WIDTH]; HEIGHT];
+    let mut set = |segment: Segments, row: usize, col: usize, c: char| {
+        if segments.contains(segment) {
+            grid[row][col] = c;
+        }
+    };
+    for col in 1..8 {
+        for (row, left, right) in [
+            (0, Segments::A1, Segments::A2),
+            (5, Segments::G1, Segments::G2),
+            (10, Segments::D1, Segments::D2),
+        ] {
+            set(left, row, col, '-');
+            set(right, row, col + 8, '-');
+        }
+    }
+    for step in 1..5 {
+        let (upper, lower) = (step, step + 5);
+        set(Segments::F, upper, 0, '|');
+        set(Segments::I, upper, 8, '|');
+        set(Segments::B, upper, 16, '|');
+        set(Segments::E, lower, 0, '|');
+        set(Segments::L, lower, 8, '|');
+        set(Segments::C, lower, 16, '|');
+        set(Segments::H, upper, step, '\\');
+        set(Segments::J, upper, 16 - step, '/');
+        set(Segments::M, lower, 5 - step, '/');
+        set(Segments::K, lower, 11 + step, '\\');
+    }
+    grid.iter()
+        .map(|row| row.iter().collect::<std::string::String>())
+        .collect::<std::vec::Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::glyphs;
+
+    #[test]
+    fn render_all_segments() {
+        let expected = [
+            " ------- ------- ",
+            "|\\      |      /|",
+            "| \\     |     / |",
+            "|  \\    |    /  |",
+            "|   \\   |   /   |",
+            " ------- ------- ",
+            "|   /   |   \\   |",
+            "|  /    |    \\  |",
+            "| /     |     \\ |",
+            "|/      |      \\|",
+            " ------- ------- ",
+        ];
+        assert_eq!(render_pattern(Segments::ALL.into()), expected.join("\n"));
+    }
+
+    #[test]
+    fn render_arrow() {
+        let expected = [
+            "                 ",
+            " \\               ",
+            "  \\              ",
+            "   \\             ",
+            "    \\            ",
+            " -------         ",
+            "    /            ",
+            "   /             ",
+            "  /              ",
+            " /               ",
+            "                 ",
+        ];
+        assert_eq!(
+            render_pattern(glyphs::ARROW_RIGHT.into()),
+            expected.join("\n")
+        );
+    }
+}
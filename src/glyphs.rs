@@ -0,0 +1,269 @@
+//! Ready-made custom characters
+//!
+//! Load them into the CGRAM with [load_glyphs()](super::HCS12SS59T::load_glyphs()):
+//!
+//! ``` text
+//! vfd.load_glyphs(&[(FontTable::Ram0, glyphs::DEGREE), (FontTable::Ram1, glyphs::OHM)])?;
+//! vfd.display("21".chars().chain([FontTable::Ram0, 'C'.into()]))?;
+//! ```
+//!
+//! Arrows only use one half of the character cell, as the diagonals all meet in the center.
+
+use super::glyph::{Glyph, Segments};
+use super::FontTable;
+
+/// Degree sign, small box in the upper left quarter
+pub const DEGREE: Glyph = Glyph::builder()
+    .with(Segments::A1)
+    .upper_left()
+    .center_left()
+    .center_upper();
+
+/// Micro sign
+pub const MICRO: Glyph = Glyph::builder()
+    .upper_left()
+    .lower_left()
+    .with(Segments::D1)
+    .center_lower();
+
+/// Ohm sign
+pub const OHM: Glyph = Glyph::builder()
+    .top()
+    .upper_left()
+    .upper_right()
+    .lower_left()
+    .lower_right();
+
+/// Arrow pointing up
+pub const ARROW_UP: Glyph = Glyph::builder()
+    .center_lower()
+    .diag_lower_left()
+    .diag_lower_right();
+
+/// Arrow pointing down
+pub const ARROW_DOWN: Glyph = ARROW_UP.mirror_vertical();
+
+/// Arrow pointing right
+pub const ARROW_RIGHT: Glyph = Glyph::builder()
+    .center_left()
+    .diag_upper_left()
+    .diag_lower_left();
+
+/// Arrow pointing left
+pub const ARROW_LEFT: Glyph = ARROW_RIGHT.mirror_horizontal();
+
+/// Bell
+pub const BELL: Glyph = Glyph::builder()
+    .top()
+    .upper_left()
+    .upper_right()
+    .center_h()
+    .center_lower();
+
+/// Heart
+pub const HEART: Glyph = Glyph::builder()
+    .top()
+    .center_upper()
+    .diag_upper_left()
+    .diag_upper_right();
+
+/// Check mark
+pub const CHECK: Glyph = Glyph::builder()
+    .lower_left()
+    .diag_lower_left()
+    .diag_upper_right();
+
+/// Cross mark
+pub const CROSS: Glyph = Glyph::builder()
+    .diag_upper_left()
+    .diag_upper_right()
+    .diag_lower_left()
+    .diag_lower_right();
+
+/// All segments lit
+pub const BLOCK: Glyph = Glyph::from_segments(Segments::ALL);
+
+/// Decimal point, used to [merge](crate::framebuffer::BufferedHCS12SS59T::set_decimal_point()) points into digits
+///
+/// The cells have no dot segment, the lower right diagonal is used as a tail instead.
+pub const DECIMAL_POINT: Glyph = Glyph::builder().diag_lower_right();
+
+/// Frames of a spoke rotating clockwise, starting at the top, see [Spinner](crate::widget::Spinner)
+pub const SPINNER: [Glyph; 8] = {
+    let g = Glyph::builder();
+    [
+        g.center_upper(),
+        g.diag_upper_right(),
+        g.center_right(),
+        g.diag_lower_right(),
+        g.center_lower(),
+        g.diag_lower_left(),
+        g.center_left(),
+        g.diag_upper_left(),
+    ]
+};
+
+/// Shape of the digits `0` to `9`
+///
+/// Resembles the built-in font, so custom characters based on digits blend in with regular text.
+pub fn digit(c: char) -> Option<Glyph> {
+    let g = Glyph::builder();
+    let glyph = match c {
+        '0' => g
+            .top()
+            .upper_left()
+            .upper_right()
+            .lower_left()
+            .lower_right()
+            .bottom(),
+        '1' => g.upper_right().lower_right(),
+        '2' => g.top().upper_right().center_h().lower_left().bottom(),
+        '3' => g.top().upper_right().center_right().lower_right().bottom(),
+        '4' => g.upper_left().upper_right().center_h().lower_right(),
+        '5' => g.top().upper_left().center_h().lower_right().bottom(),
+        '6' => g
+            .top()
+            .upper_left()
+            .center_h()
+            .lower_left()
+            .lower_right()
+            .bottom(),
+        '7' => g.top().upper_right().lower_right(),
+        '8' => g
+            .top()
+            .upper_left()
+            .upper_right()
+            .center_h()
+            .lower_left()
+            .lower_right()
+            .bottom(),
+        '9' => g
+            .top()
+            .upper_left()
+            .upper_right()
+            .center_h()
+            .lower_right()
+            .bottom(),
+        _ => return None,
+    };
+    Some(glyph)
+}
+
+/// Lowercase shape of a letter
+///
+/// Only letters with a distinct and readable lowercase shape are available,
+/// for all other characters the uppercase font is good enough and `None` is returned.
+pub fn lowercase(c: char) -> Option<Glyph> {
+    let g = Glyph::builder();
+    let glyph = match c {
+        'a' => g.center_h().lower_right().bottom().diag_lower_left(),
+        'b' => g
+            .upper_left()
+            .lower_left()
+            .center_h()
+            .lower_right()
+            .bottom(),
+        'c' => g.center_h().lower_left().bottom(),
+        'd' => g
+            .upper_right()
+            .lower_right()
+            .center_h()
+            .lower_left()
+            .bottom(),
+        'e' => g
+            .center_left()
+            .lower_left()
+            .with(Segments::D1)
+            .diag_lower_left(),
+        'f' => g.center_v().with(Segments::A2).center_h(),
+        'h' => g.upper_left().lower_left().center_h().lower_right(),
+        'i' => g.center_lower(),
+        'j' => g.center_lower().with(Segments::D1),
+        'l' => g.center_v(),
+        'm' => g.lower_left().center_h().center_lower().lower_right(),
+        'n' => g.lower_left().center_h().lower_right(),
+        'o' => g.center_h().lower_left().lower_right().bottom(),
+        'r' => g.lower_left().center_h(),
+        't' => g.upper_left().lower_left().center_left().with(Segments::D1),
+        'u' => g.lower_left().bottom().lower_right(),
+        _ => return None,
+    };
+    Some(glyph)
+}
+
+/// Shapes of the built-in font, starting at [FontTable::ChatAt]
+const ROM: [u16; 64] = [
+    0x05F7, // `@`
+    0x44CF, // `A`
+    0x153F, // `B`
+    0x00F3, // `C`
+    0x113F, // `D`
+    0x40F3, // `E`
+    0x40C3, // `F`
+    0x04FB, // `G`
+    0x44CC, // `H`
+    0x1133, // `I`
+    0x007C, // `J`
+    0x4AC0, // `K`
+    0x00F0, // `L`
+    0x82CC, // `M`
+    0x88CC, // `N`
+    0x00FF, // `O`
+    0x44C7, // `P`
+    0x08FF, // `Q`
+    0x4CC7, // `R`
+    0x44BB, // `S`
+    0x1103, // `T`
+    0x00FC, // `U`
+    0x22C0, // `V`
+    0x28CC, // `W`
+    0xAA00, // `X`
+    0x9200, // `Y`
+    0x2233, // `Z`
+    0x00F3, // `[`
+    0x8800, // `\`
+    0x003F, // `]`
+    0x2800, // `^`
+    0x0030, // `_`
+    0x0000, // SPACE
+    0x0100, // `!`
+    0x0180, // `"`
+    0x553C, // `#`
+    0x55BB, // `$`
+    0x6688, // `%`
+    0xCA73, // `&`
+    0x0200, // `'`
+    0x0A00, // `(`
+    0xA000, // `)`
+    0xFF00, // `*`
+    0x5500, // `+`
+    0x0000, // `,`
+    0x4400, // `-`
+    0x0000, // `.`
+    0x2200, // `/`
+    0x00FF, // `0`
+    0x000C, // `1`
+    0x4477, // `2`
+    0x043F, // `3`
+    0x448C, // `4`
+    0x44BB, // `5`
+    0x44FB, // `6`
+    0x000F, // `7`
+    0x44FF, // `8`
+    0x44BF, // `9`
+    0x1100, // `:`
+    0x2100, // `;`
+    0x0A00, // `<`
+    0x4430, // `=`
+    0xA000, // `>`
+    0x1407, // `?`
+];
+
+/// Approximate shape of a character of the built-in font
+///
+/// The controller's ROM is not documented segment by segment, the shapes are meant for previews on the host.
+/// `,` and `.` are blank like on the display. Returns `None` for the CGRAM addresses.
+pub fn rom(code: FontTable) -> Option<Glyph> {
+    let idx = (code as usize).checked_sub(FontTable::ChatAt as usize)?;
+    Some(Glyph::from_segments(Segments::from_bits(ROM[idx])))
+}
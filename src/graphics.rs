@@ -0,0 +1,150 @@
+//! [embedded-graphics](embedded_graphics) integration
+//!
+//! The buffered driver is a [DrawTarget] with one row of `N` character cells, every "pixel" is a character.
+//! [Text] drawables can be rendered with the [CharCellStyle] character style:
+//!
+//! ``` text
+//! Text::new("HELLO", Point::zero(), CharCellStyle).draw(&mut buffered)?;
+//! buffered.flush()?;
+//! ```
+//!
+//! [Text]: embedded_graphics::text::Text
+
+use core::convert::Infallible;
+
+use embedded_graphics::pixelcolor::PixelColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::renderer::{TextMetrics, TextRenderer};
+use embedded_graphics::text::Baseline;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::framebuffer::BufferedHCS12SS59T;
+use super::interface::Interface;
+use super::FontTable;
+
+/// "Color" of a character cell, the character shown in it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharColor(u8);
+
+impl CharColor {
+    /// The character of this cell
+    pub fn font(self) -> FontTable {
+        // Only constructed from valid font table entries
+        FontTable::try_from(self.0).unwrap()
+    }
+}
+
+impl From<FontTable> for CharColor {
+    fn from(value: FontTable) -> Self {
+        Self(value as u8)
+    }
+}
+
+impl From<char> for CharColor {
+    fn from(value: char) -> Self {
+        FontTable::from(value).into()
+    }
+}
+
+impl PixelColor for CharColor {
+    type Raw = ();
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> OriginDimensions
+    for BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    fn size(&self) -> Size {
+        Size::new(N as u32, 1)
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> DrawTarget
+    for BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    type Color = CharColor;
+    type Error = Infallible;
+
+    /// Draws into the buffer, call [flush()](BufferedHCS12SS59T::flush()) to update the display
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.y == 0 && (0..N as i32).contains(&point.x) {
+                // Address is always in range
+                let _ = self.set_char((N - 1 - point.x as usize) as u8, color.font());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Character style rendering text into character cells
+///
+/// Every character of the text occupies one cell, the baseline is ignored since there is only one row.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CharCellStyle;
+
+impl TextRenderer for CharCellStyle {
+    type Color = CharColor;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        _baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut x = position.x;
+        target.draw_iter(text.chars().map(|c| {
+            let pixel = Pixel(Point::new(x, position.y), c.into());
+            x += 1;
+            pixel
+        }))?;
+        Ok(Point::new(x, position.y))
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        _baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let space = CharColor::from(FontTable::CharSpace);
+        target.draw_iter(
+            (0..width as i32).map(|i| Pixel(Point::new(position.x + i, position.y), space)),
+        )?;
+        Ok(Point::new(position.x + width as i32, position.y))
+    }
+
+    fn measure_string(&self, text: &str, position: Point, _baseline: Baseline) -> TextMetrics {
+        let width = text.chars().count() as u32;
+        TextMetrics {
+            bounding_box: Rectangle::new(position, Size::new(width, 1)),
+            next_position: position + Point::new(width as i32, 0),
+        }
+    }
+
+    fn line_height(&self) -> u32 {
+        1
+    }
+}
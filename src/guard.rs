@@ -0,0 +1,82 @@
+use core::ops::{Deref, DerefMut};
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::interface::Interface;
+use super::{FontTable, HCS12SS59T};
+
+/// Guard blanking the display when dropped
+///
+/// Created with [HCS12SS59T::blank_on_drop()], dereferences to the driver.
+/// When the guard goes out of scope (including early returns and unwinding panics) the display is cleared
+/// and the supply voltage is turned off, so no stale text is left burning on the filament.
+pub struct BlankOnDrop<'a, IF, RstPin, VdonPin, Delay, const N: usize>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    driver: &'a mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Borrow the driver through a guard that blanks the display when dropped
+    ///
+    /// See [BlankOnDrop].
+    pub fn blank_on_drop(&mut self) -> BlankOnDrop<'_, IF, RstPin, VdonPin, Delay, N> {
+        BlankOnDrop { driver: self }
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> Deref
+    for BlankOnDrop<'_, IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    type Target = HCS12SS59T<IF, RstPin, VdonPin, Delay, N>;
+
+    fn deref(&self) -> &Self::Target {
+        self.driver
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> DerefMut
+    for BlankOnDrop<'_, IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.driver
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> Drop
+    for BlankOnDrop<'_, IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    fn drop(&mut self) {
+        // Errors can't be reported from drop, turning the supply off is tried regardless
+        let _ = self
+            .driver
+            .display(core::iter::repeat_n(FontTable::CharSpace, N));
+        let _ = self.driver.vd_off();
+    }
+}
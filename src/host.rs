@@ -0,0 +1,78 @@
+//! Helpers for running on a host with `std`, e.g. a Linux single board computer
+//!
+//! With the `linux` feature the display can be driven by a spidev device and GPIO character device pins:
+//!
+//! ``` text
+//! let spi = host::open_spidev("/dev/spidev0.0")?;
+//! let mut chip = gpio_cdev::Chip::new("/dev/gpiochip0")?;
+//! let cs = chip.get_line(8)?.request(LineRequestFlags::OUTPUT, 1, "vfd-cs")?;
+//! let n_reset = chip.get_line(25)?.request(LineRequestFlags::OUTPUT, 1, "vfd-reset")?;
+//! let mut vfd = HCS12SS59T::new_linux(spi, CdevPin::new(cs)?, Some(CdevPin::new(n_reset)?)).init()?;
+//! ```
+
+use std::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+
+#[cfg(feature = "linux")]
+use linux_embedded_hal::{CdevPin, SpidevDevice};
+
+#[cfg(feature = "linux")]
+use super::interface::SpiInterface;
+#[cfg(feature = "linux")]
+use super::state::{Ready, Uninitialized};
+#[cfg(feature = "linux")]
+use super::{NoPin, HCS12SS59T, NUM_DIGITS};
+
+/// Delay sleeping the current thread
+///
+/// Sleeping can take considerably longer than requested, which only slows down the transfers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdDelay;
+
+impl DelayNs for StdDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        std::thread::sleep(Duration::from_nanos(ns.into()));
+    }
+}
+
+/// Driver on a spidev device with GPIO character device pins, see [HCS12SS59T::new_linux()]
+#[cfg(feature = "linux")]
+pub type LinuxHCS12SS59T<S = Ready> =
+    HCS12SS59T<SpiInterface<SpidevDevice, CdevPin>, CdevPin, NoPin, StdDelay, NUM_DIGITS, S>;
+
+/// Open and configure a spidev device, e.g. `/dev/spidev0.0`
+///
+/// Configures SPI mode 3 with LSB first bit order at 500 kHz. Fails if the SPI controller does not support
+/// LSB first, like the one of the Raspberry Pi, use a [BitBangInterface](super::interface::BitBangInterface)
+/// on such boards.
+#[cfg(feature = "linux")]
+pub fn open_spidev<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<SpidevDevice> {
+    use linux_embedded_hal::spidev::{SpiModeFlags, Spidev, SpidevOptions};
+
+    let config = super::interface::recommended_spi_config();
+    let mut spi = Spidev::open(path)?;
+    let options = SpidevOptions::new()
+        .bits_per_word(8)
+        .max_speed_hz(config.max_frequency_hz)
+        .mode(SpiModeFlags::SPI_MODE_3 | SpiModeFlags::SPI_LSB_FIRST)
+        .build();
+    spi.configure(&options)?;
+    Ok(SpidevDevice(spi))
+}
+
+#[cfg(feature = "linux")]
+impl LinuxHCS12SS59T<Uninitialized> {
+    /// Create a driver for a spidev device and GPIO character device pins
+    ///
+    /// CS is driven as GPIO to meet the timing of the controller, so the chip select of the spidev device
+    /// has to be unused. Use [builder()](HCS12SS59T::builder()) with [build_with_interface()](crate::builder::Builder::build_with_interface())
+    /// for a supply pin.
+    pub fn new_linux(spi: SpidevDevice, cs: CdevPin, n_reset: Option<CdevPin>) -> Self {
+        HCS12SS59T::builder().build_with_interface(
+            SpiInterface::new(spi, Some(cs)),
+            n_reset,
+            StdDelay,
+        )
+    }
+}
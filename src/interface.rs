@@ -0,0 +1,518 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{Error as _, OutputPin};
+use embedded_hal::spi::{Mode, Operation, SpiDevice, MODE_3};
+
+use super::{Error, CGRAM_SLOTS, DCRAM_SIZE};
+
+/// Delays around one frame in microseconds
+///
+/// Derived from the [Timing](super::Timing) profile of the driver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameTiming {
+    /// Time between CS assertion and the first byte
+    pub setup: u32,
+    /// Time after every byte
+    pub gap: u32,
+    /// Additional time after the last byte before CS release
+    pub hold: u32,
+}
+
+/// Longest frame written by the driver: command and the full CGRAM
+pub(crate) const MAX_FRAME: usize = 2 * CGRAM_SLOTS + 1;
+
+/// Order in which the bits of a byte are shifted out
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BitOrder {
+    /// Least significant bit first
+    LsbFirst,
+    /// Most significant bit first
+    MsbFirst,
+}
+
+/// SPI settings of the controller, see [recommended_spi_config()]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpiConfig {
+    /// Highest clock frequency in Hz
+    pub max_frequency_hz: u32,
+    /// Clock polarity and phase
+    pub mode: Mode,
+    /// Bit order
+    pub bit_order: BitOrder,
+}
+
+/// SPI settings to configure the HAL with
+///
+/// The controller samples on the rising edge of a clock idling high (mode 3) and expects the
+/// least significant bit first, at up to 500 kHz. Most bring-up problems are a different mode or bit order.
+pub const fn recommended_spi_config() -> SpiConfig {
+    SpiConfig {
+        max_frequency_hz: 500_000,
+        mode: MODE_3,
+        bit_order: BitOrder::LsbFirst,
+    }
+}
+
+/// Copy a streamed frame into a buffer, returns the buffer and the frame length
+fn collect_frame(frame: impl IntoIterator<Item = u8>) -> Result<([u8; MAX_FRAME], usize), Error> {
+    let mut buf = [0; MAX_FRAME];
+    let mut len = 0;
+    for byte in frame {
+        *buf.get_mut(len).ok_or(Error::InvalidInput)? = byte;
+        len += 1;
+    }
+    Ok((buf, len))
+}
+
+/// Transport to the display controller
+///
+/// Implementations write one frame (command byte followed by data bytes) while the chip is selected
+/// and have to respect the given [FrameTiming].
+pub trait Interface {
+    /// Write one frame to the controller
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error>;
+
+    /// Write one frame streamed from an iterator
+    ///
+    /// By default the bytes are collected into a buffer for [write_frame()](Self::write_frame()),
+    /// frames longer than 33 bytes (command and full CGRAM) are rejected. Transports which write byte by byte
+    /// stream the bytes instead.
+    fn write_frame_iter<I, D>(
+        &mut self,
+        frame: I,
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = u8>,
+        D: DelayNs,
+    {
+        let (buf, len) = collect_frame(frame)?;
+        self.write_frame(&buf[..len], timing, delay)
+    }
+
+    /// Wrap the interface to retransmit frames after SPI errors, see [Retry]
+    fn with_retries(self, retries: u8) -> Retry<Self>
+    where
+        Self: Sized,
+    {
+        Retry::new(self, retries)
+    }
+}
+
+/// Transport which can write a frame in single steps
+///
+/// Used by [poll()](crate::HCS12SS59T::poll()) to write frames without blocking delays,
+/// the caller waits for the timing between the steps.
+pub trait StepInterface: Interface {
+    /// Select the chip before the first byte or release it after the last one
+    fn select(&mut self, selected: bool) -> Result<(), Error>;
+
+    /// Write a single byte while the chip is selected
+    fn write_byte<D: DelayNs>(&mut self, byte: u8, delay: &mut D) -> Result<(), Error>;
+}
+
+/// Interface wrapper retransmitting frames after SPI errors
+///
+/// Transient errors (e.g. a shared bus arbiter timing out) cause the whole frame to be sent again,
+/// up to `retries` times. The controller is write-only, so resending a complete frame is always safe.
+/// Pin errors are returned immediately.
+pub struct Retry<IF> {
+    inner: IF,
+    retries: u8,
+}
+
+impl<IF: Interface> Retry<IF> {
+    /// Wraps `inner`, retrying failed frames up to `retries` times
+    pub fn new(inner: IF, retries: u8) -> Self {
+        Self { inner, retries }
+    }
+
+    /// Returns the wrapped interface
+    pub fn release(self) -> IF {
+        self.inner
+    }
+}
+
+impl<IF: Interface> Interface for Retry<IF> {
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        let mut attempts = 0;
+        loop {
+            match self.inner.write_frame(frame, timing, delay) {
+                Err(Error::Spi) if attempts < self.retries => attempts += 1,
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Interface wrapper checking the setup time and the byte gaps of every frame
+///
+/// `now_us` is a free running microsecond timestamp, which is allowed to wrap around.
+/// Every byte is timestamped when the transport takes it from the frame: the first byte has to follow at least
+/// the setup time, every other byte the gap after its predecessor and the release the gap and hold after the last byte.
+/// Shorter steps mean the delays are too short, e.g. because of an inaccurate delay provider.
+/// Such frames are counted and, with the `trace` feature, logged as warning with the index of the first byte
+/// which came too early (the frame length for the release). Meant for bring-up, the frames are still sent.
+///
+/// Transports collecting the frame before writing it, e.g. a [SpiInterface] without CS-Pin, take all bytes at once,
+/// for them only the total time of the frame is checked.
+pub struct TimingCheck<IF, C> {
+    inner: IF,
+    now_us: C,
+    violations: u32,
+}
+
+impl<IF: Interface, C: FnMut() -> u32> TimingCheck<IF, C> {
+    /// Wraps `inner`, measuring the frames with the `now_us` clock
+    pub fn new(inner: IF, now_us: C) -> Self {
+        Self {
+            inner,
+            now_us,
+            violations: 0,
+        }
+    }
+
+    /// Number of frames which were written too fast
+    pub fn violations(&self) -> u32 {
+        self.violations
+    }
+
+    /// Returns the wrapped interface
+    pub fn release(self) -> IF {
+        self.inner
+    }
+}
+
+impl<IF: Interface, C: FnMut() -> u32> Interface for TimingCheck<IF, C> {
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        self.write_frame_iter(frame.iter().copied(), timing, delay)
+    }
+
+    fn write_frame_iter<I, D>(
+        &mut self,
+        frame: I,
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = u8>,
+        D: DelayNs,
+    {
+        let now_us = &mut self.now_us;
+        let start = now_us();
+        let mut last = start;
+        let mut len = 0;
+        // First step which was too short: byte index (`len` for the release), elapsed and needed time
+        let mut too_short = None;
+        let frame = frame.into_iter().inspect(|_| {
+            let now = now_us();
+            let elapsed = now.wrapping_sub(last);
+            let needed = if len == 0 { timing.setup } else { timing.gap };
+            if elapsed < needed && too_short.is_none() {
+                too_short = Some((len, elapsed, needed));
+            }
+            last = now;
+            len += 1;
+        });
+        self.inner.write_frame_iter(frame, timing, delay)?;
+
+        let end = (self.now_us)();
+        let release = end.wrapping_sub(last);
+        let total = timing.setup + timing.gap * len as u32 + timing.hold;
+        if release >= total {
+            // The frame was collected and written after the last byte was taken, only the total time counts
+            return Ok(());
+        }
+        let needed = if len == 0 {
+            timing.hold
+        } else {
+            timing.gap + timing.hold
+        };
+        if release < needed && too_short.is_none() {
+            too_short = Some((len, release, needed));
+        }
+        if let Some((_byte, _elapsed, _needed)) = too_short {
+            self.violations = self.violations.saturating_add(1);
+            #[cfg(all(feature = "trace", feature = "defmt"))]
+            defmt::warn!(
+                "frame byte {=usize} of {=usize} after {=u32} us, timing needs {=u32} us",
+                _byte,
+                len,
+                _elapsed,
+                _needed
+            );
+            #[cfg(all(feature = "trace", feature = "log", not(feature = "defmt")))]
+            log::warn!(
+                "frame byte {} of {} after {} us, timing needs {} us",
+                _byte,
+                len,
+                _elapsed,
+                _needed
+            );
+        }
+        Ok(())
+    }
+}
+
+/// SPI transport
+///
+/// Uses a dedicated CS-Pin if given, otherwise chip select is left to the [SpiDevice]
+/// and the timing is met by delay operations inside of a SPI transaction.
+pub struct SpiInterface<SPI, CsPin> {
+    spi: SPI,
+    cs: Option<CsPin>,
+}
+
+impl<SPI, CsPin> SpiInterface<SPI, CsPin>
+where
+    SPI: SpiDevice,
+    CsPin: OutputPin,
+{
+    /// Constructs a new SPI transport
+    pub fn new(spi: SPI, cs: Option<CsPin>) -> Self {
+        Self { spi, cs }
+    }
+
+    /// Returns the SPI device and CS-Pin
+    pub fn release(self) -> (SPI, Option<CsPin>) {
+        (self.spi, self.cs)
+    }
+
+    /// Write the frame as one SPI transaction, with CS handled by the [SpiDevice]
+    ///
+    /// At most 17 bytes (command and full DCRAM) can be written at once, without byte gaps
+    /// the frame is written with a single operation and not limited.
+    fn transaction(&mut self, frame: &[u8], timing: FrameTiming) -> Result<(), Error> {
+        if timing.gap == 0 {
+            return self
+                .spi
+                .transaction(&mut [
+                    Operation::DelayNs(timing.setup.saturating_mul(1000)),
+                    Operation::Write(frame),
+                    Operation::DelayNs(timing.hold.saturating_mul(1000)),
+                ])
+                .map_err(|_| Error::Spi);
+        }
+        const MAX_OPS: usize = 2 * (DCRAM_SIZE + 1) + 2;
+        if 2 * frame.len() + 2 > MAX_OPS {
+            return Err(Error::InvalidInput);
+        }
+        let mut ops = [const { Operation::DelayNs(0) }; MAX_OPS];
+        ops[0] = Operation::DelayNs(timing.setup.saturating_mul(1000));
+        let mut len = 1;
+        for byte in frame.chunks(1) {
+            ops[len] = Operation::Write(byte);
+            ops[len + 1] = Operation::DelayNs(timing.gap.saturating_mul(1000));
+            len += 2;
+        }
+        ops[len] = Operation::DelayNs(timing.hold.saturating_mul(1000));
+        len += 1;
+        self.spi
+            .transaction(&mut ops[..len])
+            .map_err(|_| Error::Spi)
+    }
+}
+
+impl<SPI, CsPin> Interface for SpiInterface<SPI, CsPin>
+where
+    SPI: SpiDevice,
+    CsPin: OutputPin,
+{
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        if self.cs.is_none() {
+            return self.transaction(frame, timing);
+        }
+        self.write_frame_iter(frame.iter().copied(), timing, delay)
+    }
+
+    /// Streams the bytes with a CS-Pin, without one the frame is collected for a single transaction
+    fn write_frame_iter<I, D>(
+        &mut self,
+        frame: I,
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = u8>,
+        D: DelayNs,
+    {
+        let Some(cs) = &mut self.cs else {
+            let (buf, len) = collect_frame(frame)?;
+            return self.transaction(&buf[..len], timing);
+        };
+        cs.set_low().map_err(|e| Error::ChipSelect(e.kind()))?;
+        delay.delay_us(timing.setup);
+        let written = frame.into_iter().try_for_each(|byte| {
+            self.spi.write(&[byte]).map_err(|_| Error::Spi)?;
+            if timing.gap > 0 {
+                delay.delay_us(timing.gap);
+            }
+            Ok(())
+        });
+        if written.is_ok() && timing.hold > 0 {
+            delay.delay_us(timing.hold);
+        }
+        // Release CS on errors as well, so the frame can be sent again
+        cs.set_high().map_err(|e| Error::ChipSelect(e.kind()))?;
+        written
+    }
+}
+
+/// Needs a dedicated CS-Pin, [select()](StepInterface::select()) fails with [Error::InvalidInput] without one
+impl<SPI, CsPin> StepInterface for SpiInterface<SPI, CsPin>
+where
+    SPI: SpiDevice,
+    CsPin: OutputPin,
+{
+    fn select(&mut self, selected: bool) -> Result<(), Error> {
+        let cs = self.cs.as_mut().ok_or(Error::InvalidInput)?;
+        if selected {
+            cs.set_low().map_err(|e| Error::ChipSelect(e.kind()))
+        } else {
+            cs.set_high().map_err(|e| Error::ChipSelect(e.kind()))
+        }
+    }
+
+    fn write_byte<D: DelayNs>(&mut self, byte: u8, _delay: &mut D) -> Result<(), Error> {
+        self.spi.write(&[byte]).map_err(|_| Error::Spi)
+    }
+}
+
+/// Bit-banged 3-wire transport
+///
+/// Drives clock, data and CS with plain GPIOs, for setups without a (slow enough) SPI peripheral.
+/// Data is shifted out LSB first with the clock idling high and sampled on the rising edge,
+/// like SPI mode 3 with LSB first bit order.
+pub struct BitBangInterface<ClkPin, DataPin, CsPin> {
+    clk: ClkPin,
+    data: DataPin,
+    cs: CsPin,
+    half_period_ns: u32,
+}
+
+impl<ClkPin, DataPin, CsPin> BitBangInterface<ClkPin, DataPin, CsPin>
+where
+    ClkPin: OutputPin,
+    DataPin: OutputPin,
+    CsPin: OutputPin,
+{
+    /// Constructs a new bit-banged transport
+    ///
+    /// `half_period_ns` is the time the clock stays low and high, 1000 results in a 500 kHz clock.
+    pub fn new(clk: ClkPin, data: DataPin, cs: CsPin, half_period_ns: u32) -> Self {
+        Self {
+            clk,
+            data,
+            cs,
+            half_period_ns,
+        }
+    }
+
+    /// Returns the clock, data and CS-Pin
+    pub fn release(self) -> (ClkPin, DataPin, CsPin) {
+        (self.clk, self.data, self.cs)
+    }
+
+    fn shift_out<D: DelayNs>(&mut self, byte: u8, delay: &mut D) -> Result<(), Error> {
+        for bit in 0..8 {
+            self.clk.set_low().map_err(|e| Error::Clock(e.kind()))?;
+            if byte & (1 << bit) != 0 {
+                self.data.set_high().map_err(|e| Error::Data(e.kind()))?;
+            } else {
+                self.data.set_low().map_err(|e| Error::Data(e.kind()))?;
+            }
+            delay.delay_ns(self.half_period_ns);
+            self.clk.set_high().map_err(|e| Error::Clock(e.kind()))?;
+            delay.delay_ns(self.half_period_ns);
+        }
+        Ok(())
+    }
+}
+
+impl<ClkPin, DataPin, CsPin> Interface for BitBangInterface<ClkPin, DataPin, CsPin>
+where
+    ClkPin: OutputPin,
+    DataPin: OutputPin,
+    CsPin: OutputPin,
+{
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error> {
+        self.write_frame_iter(frame.iter().copied(), timing, delay)
+    }
+
+    fn write_frame_iter<I, D>(
+        &mut self,
+        frame: I,
+        timing: FrameTiming,
+        delay: &mut D,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = u8>,
+        D: DelayNs,
+    {
+        self.clk.set_high().map_err(|e| Error::Clock(e.kind()))?;
+        self.cs.set_low().map_err(|e| Error::ChipSelect(e.kind()))?;
+        delay.delay_us(timing.setup);
+        let written = frame.into_iter().try_for_each(|byte| {
+            self.shift_out(byte, delay)?;
+            if timing.gap > 0 {
+                delay.delay_us(timing.gap);
+            }
+            Ok(())
+        });
+        if written.is_ok() && timing.hold > 0 {
+            delay.delay_us(timing.hold);
+        }
+        // Release CS on errors as well, so the frame can be sent again
+        self.cs
+            .set_high()
+            .map_err(|e| Error::ChipSelect(e.kind()))?;
+        written
+    }
+}
+
+impl<ClkPin, DataPin, CsPin> StepInterface for BitBangInterface<ClkPin, DataPin, CsPin>
+where
+    ClkPin: OutputPin,
+    DataPin: OutputPin,
+    CsPin: OutputPin,
+{
+    fn select(&mut self, selected: bool) -> Result<(), Error> {
+        if selected {
+            self.clk.set_high().map_err(|e| Error::Clock(e.kind()))?;
+            self.cs.set_low().map_err(|e| Error::ChipSelect(e.kind()))
+        } else {
+            self.cs.set_high().map_err(|e| Error::ChipSelect(e.kind()))
+        }
+    }
+
+    fn write_byte<D: DelayNs>(&mut self, byte: u8, delay: &mut D) -> Result<(), Error> {
+        self.shift_out(byte, delay)
+    }
+}
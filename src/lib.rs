@@ -1,199 +1,1216 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(all(feature = "trace", not(any(feature = "defmt", feature = "log"))))]
+compile_error!("the `trace` feature needs the `defmt` or `log` feature as output");
+
 mod font;
-pub use font::FontTable;
+pub use font::{CharMapper, DefaultMapper, FontTable};
 
 pub mod animation;
+mod batch;
+pub use batch::Batch;
+mod buffer;
+pub use buffer::DisplayBuffer;
+pub mod builder;
+mod chain;
+#[cfg(feature = "embedded-hal-02")]
+pub mod compat;
+pub use chain::DisplayChain;
+mod display;
+pub use display::SegmentTextDisplay;
+#[cfg(feature = "embedded-dma")]
+pub mod dma;
+mod fade;
+pub mod format;
+pub mod framebuffer;
+pub mod glyph;
+pub mod glyphs;
+#[cfg(feature = "embedded-graphics")]
+pub mod graphics;
+mod guard;
+#[cfg(feature = "std")]
+pub mod host;
+pub use guard::BlankOnDrop;
+pub mod interface;
+mod nonblocking;
+pub use nonblocking::PendingWrite;
+pub mod region;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+pub mod split;
+pub mod widget;
+use interface::{FrameTiming, Interface, SpiInterface};
+
+pub mod state {
+    /// Initialization state of the driver
+    pub trait State {}
+    /// Driver which has to be initialized before use
+    pub struct Uninitialized;
+    impl State for Uninitialized {}
+    /// Initialized driver, ready to display things
+    pub struct Ready;
+    impl State for Ready {}
+    /// Driver in the middle of a non-blocking initialization
+    pub struct Initializing;
+    impl State for Initializing {}
+}
+use state::*;
+
+use core::marker::PhantomData;
 
-use embedded_hal::digital::OutputPin;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, Error as _, ErrorType, OutputPin};
 use embedded_hal::spi::SpiDevice;
 
+/// Number of digits of the HCS-12SS59T
 const NUM_DIGITS: usize = 12;
+/// Size of the controllers DCRAM, the upper limit for the number of digits
+const DCRAM_SIZE: usize = 16;
+/// Number of CGRAM slots of the controller
+const CGRAM_SLOTS: usize = 16;
 
+/// Commands of the controller
+///
+/// The command is sent in the upper four bits of the first byte, the lower four bits carry the argument.
+/// Send single byte commands with [HCS12SS59T::send()].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
-#[allow(dead_code)]
-enum Command {
+pub enum Command {
+    /// Write character codes to the display RAM, the argument is the start address
     DCRamWrite = 0x10,
+    /// Write patterns to the character generator RAM, the argument is the start slot
     CGRamWrite = 0x20,
+    /// Write auxiliary segment bits, the argument is the start address
     ADRamWrite = 0x30,
+    /// Set the duty cycle `0..=15`
     DisplayDutySet = 0x50,
+    /// Set the number of digits, `0` selects 16
     NumDigitsSet = 0x60,
+    /// Set the [Lights] mode
     Lights = 0x70,
 }
+
+/// Log a frame before it is sent, the command name is decoded from the upper nibble of the first byte
+#[cfg(feature = "trace")]
+fn trace_frame(frame: &[u8]) {
+    let Some((&command, payload)) = frame.split_first() else {
+        return;
+    };
+    let name = match command & 0xF0 {
+        c if c == Command::DCRamWrite as u8 => "DCRamWrite",
+        c if c == Command::CGRamWrite as u8 => "CGRamWrite",
+        c if c == Command::ADRamWrite as u8 => "ADRamWrite",
+        c if c == Command::DisplayDutySet as u8 => "DisplayDutySet",
+        c if c == Command::NumDigitsSet as u8 => "NumDigitsSet",
+        c if c == Command::Lights as u8 => "Lights",
+        _ => "Unknown",
+    };
+    let arg = command & 0x0F;
+    #[cfg(feature = "defmt")]
+    defmt::trace!("{=str} {=u8:#x} {=[u8]:02x}", name, arg, payload);
+    #[cfg(all(feature = "log", not(feature = "defmt")))]
+    log::trace!("{} {:#x} {:02x?}", name, arg, payload);
+}
+
+/// Lights mode of the controller
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
-#[allow(dead_code)]
-enum Lights {
+pub enum Lights {
+    /// Show the display RAM content
     Normal = 0x00,
+    /// All segments off
     Off = 0x01,
+    /// All segments on, e.g. for a display test
     On = 0x02,
 }
 
+/// Placeholder for pins which are not connected
+///
+/// Can be used as type for optional pins, e.g. `None::<NoPin>` for the supply pin
+/// or the reset pin, and as `CsPin` type of [HCS12SS59T::new_without_cs()].
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Active level of the reset and supply pins
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Polarity {
+    /// The pin is active when low, like the reset and supply inputs of the module
+    #[default]
+    ActiveLow,
+    /// The pin is active when high, e.g. the enable input of many boost converters
+    ActiveHigh,
+}
+
+impl Polarity {
+    /// Drive `pin` to the active or inactive level
+    fn set<P: OutputPin>(self, pin: &mut P, active: bool) -> Result<(), P::Error> {
+        if active == (self == Polarity::ActiveHigh) {
+            pin.set_high()
+        } else {
+            pin.set_low()
+        }
+    }
+}
+
+/// Content of one ADRAM address
+///
+/// Each digit has two additional bits in ADRAM, which drive auxiliary segments (grid symbols)
+/// on modules that have them connected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdramBits(u8);
+
+impl AdramBits {
+    /// No auxiliary segment
+    pub const NONE: Self = Self(0b00);
+    /// First auxiliary segment (bit 0)
+    pub const SEG1: Self = Self(0b01);
+    /// Second auxiliary segment (bit 1)
+    pub const SEG2: Self = Self(0b10);
+    /// Both auxiliary segments
+    pub const BOTH: Self = Self(0b11);
+
+    /// Raw value of the two bits
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns wether all segments of `other` are set
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl From<u8> for AdramBits {
+    /// Converts the lower two bits, higher bits are ignored
+    fn from(value: u8) -> Self {
+        Self(value & 0b11)
+    }
+}
+
+impl core::ops::BitOr for AdramBits {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Timing profile of the serial interface and the initialization
+///
+/// All values are in microseconds. The defaults satisfy the HCS-12SS59T datasheet,
+/// slow or long wires might need longer delays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timing {
+    /// Length of the reset pulse
+    pub reset_pulse: u32,
+    /// Wait time after reset
+    pub reset_recovery: u32,
+    /// Time between CS assertion and a single byte command
+    pub cmd_setup: u32,
+    /// Time between a single byte command and CS release
+    pub cmd_hold: u32,
+    /// Time between CS assertion and the first byte of a data write
+    pub setup: u32,
+    /// Time after every byte of a data write
+    pub byte_gap: u32,
+    /// Additional time after the last byte of a data write before CS release
+    pub hold: u32,
+    /// Duration of the brightness ramp of [init_soft()](HCS12SS59T::init_soft())
+    pub soft_start: u32,
+}
+
+impl Timing {
+    /// Drop the byte gap if shifting out one byte at `spi_hz` already takes as long
+    ///
+    /// With a slow clock the transfer itself satisfies the gap, e.g. a byte takes 32 µs at 250 kHz,
+    /// and the extra delays would only prolong the frames.
+    pub const fn for_spi_clock(mut self, spi_hz: u32) -> Self {
+        if spi_hz > 0 && 8_000_000 / spi_hz >= self.byte_gap {
+            self.byte_gap = 0;
+        }
+        self
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            reset_pulse: 25,
+            reset_recovery: 5,
+            cmd_setup: 5,
+            cmd_hold: 20,
+            setup: 1,
+            byte_gap: 8,
+            hold: 12,
+            soft_start: 300_000,
+        }
+    }
+}
+
+/// Driver errors
+///
+/// Pin errors identify the failing pin and carry the [ErrorKind](embedded_hal::digital::ErrorKind) reported by the HAL.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
+    /// SPI transfer failed
     Spi,
-    Gpio,
+    /// Setting the reset pin failed
+    Reset(digital::ErrorKind),
+    /// Setting the CS-Pin failed
+    ChipSelect(digital::ErrorKind),
+    /// Setting the supply pin failed
+    Vdon(digital::ErrorKind),
+    /// Setting the clock pin of a [BitBangInterface](interface::BitBangInterface) failed
+    Clock(digital::ErrorKind),
+    /// Setting the data pin of a [BitBangInterface](interface::BitBangInterface) failed
+    Data(digital::ErrorKind),
+    /// Argument out of range
     InvalidInput,
 }
 
-pub struct HCS12SS59T<SPI, RstPin, VdonPin, Delay, CsPin> {
-    spi: SPI,
-    n_reset: RstPin,
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Spi => f.write_str("SPI transfer failed"),
+            Error::Reset(kind) => write!(f, "setting the reset pin failed: {kind}"),
+            Error::ChipSelect(kind) => write!(f, "setting the CS pin failed: {kind}"),
+            Error::Vdon(kind) => write!(f, "setting the supply pin failed: {kind}"),
+            Error::Clock(kind) => write!(f, "setting the clock pin failed: {kind}"),
+            Error::Data(kind) => write!(f, "setting the data pin failed: {kind}"),
+            Error::InvalidInput => f.write_str("argument out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Error of [display_str_strict()](HCS12SS59T::display_str_strict())
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StrictError {
+    /// The character at `index` of the text is not in the font table, nothing was written
+    InvalidChar {
+        /// Position of the character in the text, counted in characters
+        index: usize,
+    },
+    /// Writing the text failed
+    Driver(Error),
+}
+
+impl From<Error> for StrictError {
+    fn from(error: Error) -> Self {
+        StrictError::Driver(error)
+    }
+}
+
+impl core::fmt::Display for StrictError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StrictError::InvalidChar { index } => {
+                write!(f, "character {index} is not in the font table")
+            }
+            StrictError::Driver(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StrictError {}
+
+/// HCS-12SS59T driver
+///
+/// `N` is the number of connected digits, which defaults to the 12 digits of the HCS-12SS59T.
+/// Other modules with the same controller can use up to 16 digits.
+///
+/// A new driver is [Uninitialized] and has to be turned into a [Ready] driver with [init()](Self::init()).
+pub struct HCS12SS59T<IF, RstPin, VdonPin, Delay, const N: usize = NUM_DIGITS, S = Ready> {
+    interface: IF,
+    n_reset: Option<RstPin>,
     n_vdon: Option<VdonPin>,
+    reset_polarity: Polarity,
+    vdon_polarity: Polarity,
     delay: Delay,
-    cs: CsPin,
+    timing: Timing,
+    fill: FontTable,
+    mapper: &'static dyn CharMapper,
+    init_brightness: u8,
+    brightness: u8,
+    brightness_ramp_ms: u32,
+    lights: Lights,
+    supply_on: bool,
+    blink: Option<animation::Blink>,
+    init_step: u8,
+    init_since: u32,
+    _state: PhantomData<S>,
+}
+
+impl HCS12SS59T<(), (), (), ()> {
+    /// Returns a [Builder](builder::Builder) to construct a driver with non-default settings
+    pub fn builder() -> builder::Builder<NoPin> {
+        builder::Builder::new()
+    }
 }
 
-impl<SPI, RstPin, VdonPin, Delay, CsPin> HCS12SS59T<SPI, RstPin, VdonPin, Delay, CsPin>
+impl<SPI, RstPin, VdonPin, Delay, CsPin>
+    HCS12SS59T<SpiInterface<SPI, CsPin>, RstPin, VdonPin, Delay, NUM_DIGITS, Uninitialized>
 where
     SPI: SpiDevice,
     RstPin: OutputPin,
     VdonPin: OutputPin,
     CsPin: OutputPin,
-    Delay: embedded_hal::delay::DelayUs,
+    Delay: DelayNs,
 {
     /// Constructs a new HCS12SS59T
     ///
-    /// Initialization has to be done seperately by calling [init()](Self::init()).
+    /// The returned driver is [Uninitialized], initialization has to be done seperately by calling [init()](Self::init()).
+    ///
+    /// It is necessary to have a dedicated CS-Pin and a [DelayNs] due to timing restrictions of the HCS-12SS59T.
     ///
-    /// It is necessary to have a dedicated CS-Pin and a [Delay] due to timing restrictions of the HCS-12SS59T.
+    /// The reset pin is optional for boards where /RST is driven by the MCU reset or an RC circuit.
     pub fn new(
         spi: SPI,
-        n_reset: RstPin,
+        n_reset: Option<RstPin>,
         delay: Delay,
         n_vdon: Option<VdonPin>,
         cs: CsPin,
     ) -> Self {
+        Self::new_with_digits(spi, n_reset, delay, n_vdon, Some(cs))
+    }
+}
+
+impl<SPI, RstPin, VdonPin, Delay>
+    HCS12SS59T<SpiInterface<SPI, NoPin>, RstPin, VdonPin, Delay, NUM_DIGITS, Uninitialized>
+where
+    SPI: SpiDevice,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Constructs a new HCS12SS59T without a dedicated CS-Pin
+    ///
+    /// Chip select is left to the [SpiDevice], the timing of the HCS-12SS59T is met by
+    /// delay operations inside of the SPI transactions.
+    /// The HAL has to support [Operation::DelayNs](embedded_hal::spi::Operation::DelayNs) for this to work.
+    pub fn new_without_cs(
+        spi: SPI,
+        n_reset: Option<RstPin>,
+        delay: Delay,
+        n_vdon: Option<VdonPin>,
+    ) -> Self {
+        Self::new_with_digits(spi, n_reset, delay, n_vdon, None)
+    }
+}
+
+impl<SPI, RstPin, VdonPin, Delay, CsPin, const N: usize>
+    HCS12SS59T<SpiInterface<SPI, CsPin>, RstPin, VdonPin, Delay, N, Uninitialized>
+where
+    SPI: SpiDevice,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    CsPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Constructs a new driver for a display with `N` digits
+    ///
+    /// Same as [new()](HCS12SS59T::new()), `N` has to be in the range `1..=16`.
+    /// Without `cs` the chip select is handled by the [SpiDevice], see [new_without_cs()](HCS12SS59T::new_without_cs()).
+    pub fn new_with_digits(
+        spi: SPI,
+        n_reset: Option<RstPin>,
+        delay: Delay,
+        n_vdon: Option<VdonPin>,
+        cs: Option<CsPin>,
+    ) -> Self {
+        Self::with_interface(SpiInterface::new(spi, cs), n_reset, delay, n_vdon)
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize>
+    HCS12SS59T<IF, RstPin, VdonPin, Delay, N, Uninitialized>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Constructs a new driver on top of an arbitrary [Interface]
+    ///
+    /// `N` has to be in the range `1..=16`.
+    pub fn with_interface(
+        interface: IF,
+        n_reset: Option<RstPin>,
+        delay: Delay,
+        n_vdon: Option<VdonPin>,
+    ) -> Self {
+        const { assert!(N >= 1 && N <= DCRAM_SIZE) };
         Self {
-            spi,
+            interface,
             n_reset,
             n_vdon,
+            reset_polarity: Polarity::ActiveLow,
+            vdon_polarity: Polarity::ActiveLow,
             delay,
-            cs,
+            timing: Timing::default(),
+            fill: FontTable::CharSpace,
+            mapper: &DefaultMapper,
+            init_brightness: 7,
+            brightness: 7,
+            brightness_ramp_ms: 0,
+            lights: Lights::Normal,
+            supply_on: false,
+            blink: None,
+            init_step: 0,
+            init_since: 0,
+            _state: PhantomData,
         }
     }
 
-    pub fn destroy(self) -> (SPI, RstPin, Delay, Option<VdonPin>, CsPin) {
-        (self.spi, self.n_reset, self.delay, self.n_vdon, self.cs)
-    }
-
     /// Initialize the VFD display
     ///
-    /// Resets the display, turns on the supply voltage and sets brightness to 7.
-    pub fn init(&mut self) -> Result<(), Error> {
-        self.n_reset.set_low().map_err(|_| Error::Gpio)?;
-        self.delay.delay_us(25);
-        self.n_reset.set_high().map_err(|_| Error::Gpio)?;
-        self.delay.delay_us(5);
+    /// Resets the display (if reset pin is configured), turns on the supply voltage and sets brightness to 7 (or the brightness set by the [Builder](builder::Builder)).
+    /// Consumes the uninitialized driver and returns a [Ready] one.
+    pub fn init(mut self) -> Result<HCS12SS59T<IF, RstPin, VdonPin, Delay, N>, Error> {
+        if self.n_reset.is_some() {
+            self.reset(true)?;
+            self.delay.delay_us(self.timing.reset_pulse);
+            self.reset(false)?;
+            self.delay.delay_us(self.timing.reset_recovery);
+        }
 
-        self.vd_on()?;
+        self.supply(true)?;
 
-        self.send_cmd(Command::NumDigitsSet, NUM_DIGITS as u8)?;
-        self.send_cmd(Command::DisplayDutySet, 7)?;
+        // 16 digits are encoded as 0
+        self.send_cmd(Command::NumDigitsSet, N as u8)?;
+        self.send_cmd(Command::DisplayDutySet, self.init_brightness)?;
+        self.brightness = self.init_brightness;
         self.send_cmd(Command::Lights, Lights::Normal as u8)?;
 
+        Ok(self.into_state())
+    }
+
+    /// Initialize the VFD display with a soft start
+    ///
+    /// Same as [init()](Self::init()), but the display starts at the lowest brightness and is ramped up to the
+    /// initial brightness over [Timing::soft_start] (300 ms by default). Reduces the inrush current and the stress
+    /// on the filament of old tubes.
+    pub fn init_soft(mut self) -> Result<HCS12SS59T<IF, RstPin, VdonPin, Delay, N>, Error> {
+        let target = self.init_brightness;
+        self.init_brightness = target.min(1);
+        let mut vfd = self.init()?;
+        vfd.init_brightness = target;
+        let step_us = vfd.timing.soft_start / (target.max(2) - 1) as u32;
+        for level in 2..=target {
+            vfd.delay.delay_us(step_us);
+            vfd.set_duty(level)?;
+        }
+        Ok(vfd)
+    }
+
+    /// Start a non-blocking initialization
+    ///
+    /// Same as [init()](Self::init()), but instead of waiting for the reset timing with the delay provider
+    /// the returned driver has to be advanced with [init_poll()](HCS12SS59T::init_poll()).
+    ///
+    /// `now_us` is a free running microsecond timestamp, which is allowed to wrap around.
+    pub fn init_start(
+        mut self,
+        now_us: u32,
+    ) -> Result<HCS12SS59T<IF, RstPin, VdonPin, Delay, N, Initializing>, Error> {
+        if self.n_reset.is_some() {
+            self.reset(true)?;
+            self.init_step = 0;
+        } else {
+            // Without reset pin only the supply has to be switched on
+            self.init_step = 1;
+        }
+        self.init_since = now_us;
+        Ok(self.into_state())
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize>
+    HCS12SS59T<IF, RstPin, VdonPin, Delay, N, Initializing>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Advance the initialization started with [init_start()](HCS12SS59T::init_start())
+    ///
+    /// Every call does at most one step (a pin change or a single command) and returns
+    /// [WouldBlock](nb::Error::WouldBlock) until the initialization is finished.
+    /// Afterwards the [Ready] driver can be obtained with [into_ready()](Self::into_ready()).
+    pub fn init_poll(&mut self, now_us: u32) -> nb::Result<(), Error> {
+        let elapsed = now_us.wrapping_sub(self.init_since);
+        match self.init_step {
+            0 if elapsed >= self.timing.reset_pulse => {
+                self.reset(false)?;
+                self.init_since = now_us;
+            }
+            1 if self.n_reset.is_none() || elapsed >= self.timing.reset_recovery => {
+                self.supply(true)?
+            }
+            // 16 digits are encoded as 0
+            2 => self.send_cmd(Command::NumDigitsSet, N as u8)?,
+            3 => {
+                self.send_cmd(Command::DisplayDutySet, self.init_brightness)?;
+                self.brightness = self.init_brightness;
+            }
+            4 => self.send_cmd(Command::Lights, Lights::Normal as u8)?,
+            5.. => return Ok(()),
+            _ => return Err(nb::Error::WouldBlock),
+        }
+        self.init_step += 1;
+        if self.init_step > 4 {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Returns the [Ready] driver once [init_poll()](Self::init_poll()) has finished, otherwise the driver is handed back
+    pub fn into_ready(self) -> Result<HCS12SS59T<IF, RstPin, VdonPin, Delay, N>, Self> {
+        if self.init_step > 4 {
+            Ok(self.into_state())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize, S> HCS12SS59T<IF, RstPin, VdonPin, Delay, N, S>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Returns the interface and pins
+    ///
+    /// For SPI see [SpiInterface::release()].
+    pub fn destroy(self) -> (IF, Option<RstPin>, Delay, Option<VdonPin>) {
+        (self.interface, self.n_reset, self.delay, self.n_vdon)
+    }
+
+    /// Access the interface, e.g. the draw target of a simulator
+    ///
+    /// Frames written directly bypass the driver state.
+    pub fn interface(&mut self) -> &mut IF {
+        &mut self.interface
+    }
+
+    /// Retransmit frames after SPI errors up to `retries` times
+    ///
+    /// Wraps the interface into a [Retry](interface::Retry).
+    pub fn with_retries(
+        self,
+        retries: u8,
+    ) -> HCS12SS59T<interface::Retry<IF>, RstPin, VdonPin, Delay, N, S> {
+        self.map_interface(|interface| interface.with_retries(retries))
+    }
+
+    /// Count and log frames written faster than the timing allows, for bring-up
+    ///
+    /// Wraps the interface into a [TimingCheck](interface::TimingCheck) measuring with the `now_us` clock.
+    pub fn with_timing_check<C: FnMut() -> u32>(
+        self,
+        now_us: C,
+    ) -> HCS12SS59T<interface::TimingCheck<IF, C>, RstPin, VdonPin, Delay, N, S> {
+        self.map_interface(|interface| interface::TimingCheck::new(interface, now_us))
+    }
+
+    /// Change the [CharMapper] used by [display_str()](HCS12SS59T::display_str())
+    pub fn set_char_mapper(&mut self, mapper: &'static dyn CharMapper) {
+        self.mapper = mapper;
+    }
+
+    /// Ramp the brightness over `ramp_ms` when [brightness()](HCS12SS59T::brightness()) is called, `0` changes it immediately
+    ///
+    /// The ramp blocks like [fade_to()](HCS12SS59T::fade_to()), use `brightness_async()` of the `async` feature
+    /// to wait with an async delay.
+    pub fn set_brightness_ramp(&mut self, ramp_ms: u32) {
+        self.brightness_ramp_ms = ramp_ms;
+    }
+
+    fn into_state<T>(self) -> HCS12SS59T<IF, RstPin, VdonPin, Delay, N, T> {
+        self.map_interface(|interface| interface)
+    }
+
+    fn map_interface<J, T>(
+        self,
+        f: impl FnOnce(IF) -> J,
+    ) -> HCS12SS59T<J, RstPin, VdonPin, Delay, N, T> {
+        HCS12SS59T {
+            interface: f(self.interface),
+            n_reset: self.n_reset,
+            n_vdon: self.n_vdon,
+            reset_polarity: self.reset_polarity,
+            vdon_polarity: self.vdon_polarity,
+            delay: self.delay,
+            timing: self.timing,
+            fill: self.fill,
+            mapper: self.mapper,
+            init_brightness: self.init_brightness,
+            brightness: self.brightness,
+            brightness_ramp_ms: self.brightness_ramp_ms,
+            lights: self.lights,
+            supply_on: self.supply_on,
+            blink: self.blink,
+            init_step: self.init_step,
+            init_since: self.init_since,
+            _state: PhantomData,
+        }
+    }
+
+    /// Assert or release the reset pin (if reset pin is configured)
+    fn reset(&mut self, active: bool) -> Result<(), Error> {
+        if let Some(pin) = &mut self.n_reset {
+            self.reset_polarity
+                .set(pin, active)
+                .map_err(|e| Error::Reset(e.kind()))?;
+        }
         Ok(())
     }
 
-    /// Turns the supply voltage off (if supply pin is configured)
-    pub fn vd_off(&mut self) -> Result<(), Error> {
+    /// Switch the supply voltage (if supply pin is configured)
+    fn supply(&mut self, on: bool) -> Result<(), Error> {
         if let Some(pin) = &mut self.n_vdon {
-            pin.set_high().map_err(|_| Error::Gpio)?; // Display voltage OFF
+            self.vdon_polarity
+                .set(pin, on)
+                .map_err(|e| Error::Vdon(e.kind()))?;
         }
+        self.supply_on = on;
         Ok(())
     }
 
+    /// Send one command byte with with four bits argument payload
+    ///
+    /// (The higher four bit specify the command, the lower four bit are the argument)
+    fn send_cmd(&mut self, cmd: Command, arg: u8) -> Result<(), Error> {
+        let arg = arg & 0x0F;
+        let command = [cmd as u8 | arg];
+        self.transfer(&command, self.timing.cmd_setup, self.timing.cmd_hold, 0)
+    }
+
+    /// Write one frame with the given delays through the interface
+    ///
+    /// With the `critical-section` feature interrupts can't stretch the timing while CS is asserted.
+    fn transfer(&mut self, buf: &[u8], setup: u32, gap: u32, hold: u32) -> Result<(), Error> {
+        let timing = FrameTiming { setup, gap, hold };
+        #[cfg(feature = "trace")]
+        trace_frame(buf);
+        #[cfg(feature = "critical-section")]
+        return critical_section::with(|_| {
+            self.interface.write_frame(buf, timing, &mut self.delay)
+        });
+        #[cfg(not(feature = "critical-section"))]
+        self.interface.write_frame(buf, timing, &mut self.delay)
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N, Ready>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Turns the supply voltage off (if supply pin is configured)
+    ///
+    /// Does nothing without a supply pin, use [blank()](Self::blank()) or [brightness(0)](Self::brightness())
+    /// to turn the display off in that case.
+    pub fn vd_off(&mut self) -> Result<(), Error> {
+        self.supply(false)
+    }
+
     /// Turns the supply voltage on (if supply pin is configured)
     pub fn vd_on(&mut self) -> Result<(), Error> {
-        if let Some(pin) = &mut self.n_vdon {
-            pin.set_low().map_err(|_| Error::Gpio)?; // Display voltage ON
-        }
-        Ok(())
+        self.supply(true)
     }
 
     /// Set the brightness (duty cycle) of the Display
     ///
-    /// Turns the display off when brightness is `0` and on when brightness is `1..15`.
+    /// `0` blanks the display and drops the duty cycle to the minimum, `1..=15` lights it again.
+    /// The supply voltage is not touched, it is switched with [vd_off()](Self::vd_off()) and [vd_on()](Self::vd_on()).
+    /// With a [ramp](Self::set_brightness_ramp()) the brightness fades to the new value.
     pub fn brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        if self.brightness_ramp_ms > 0 {
+            return self.fade_to(brightness, self.brightness_ramp_ms);
+        }
+        self.set_duty(brightness)
+    }
+
+    /// The brightness last set, it is kept while the display is off
+    pub fn current_brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Returns wether the display shows its content, i.e. the supply is on and it is not blanked or in standby
+    pub fn is_display_on(&self) -> bool {
+        self.is_supply_on() && self.lights != Lights::Off
+    }
+
+    /// Returns wether the supply voltage is on, always `true` without a supply pin
+    pub fn is_supply_on(&self) -> bool {
+        self.n_vdon.is_none() || self.supply_on
+    }
+
+    /// Set the brightness in percent of the perceived maximum
+    ///
+    /// Maps through a perceptual (gamma 2.2) curve onto the 15 duty steps, so equal steps in percent look like equal
+    /// steps in brightness. The bright end gets the finer resolution, everything up to 35 % is the lowest duty step.
+    /// `0` turns the display off, use [brightness()](Self::brightness()) for the raw hardware values.
+    pub fn set_brightness_percent(&mut self, percent: u8) -> Result<(), Error> {
+        /// Lowest percentage of the duty steps 2 to 15, `100 * ((duty - 0.5) / 15) ^ (1 / 2.2)`
+        const THRESHOLDS: [u8; 14] = [36, 45, 52, 58, 64, 69, 73, 78, 82, 86, 89, 93, 96, 99];
+        let duty = match percent {
+            0 => 0,
+            1..=100 => 1 + THRESHOLDS.iter().filter(|t| **t <= percent).count() as u8,
+            _ => return Err(Error::InvalidInput),
+        };
+        self.brightness(duty)
+    }
+
+    /// Set the brightness immediately, see [brightness()](Self::brightness())
+    pub(crate) fn set_duty(&mut self, brightness: u8) -> Result<(), Error> {
         match brightness {
-            0 => self.vd_off(),
+            // The previous brightness is kept for the next fade in
+            0 => {
+                self.set_lights(Lights::Off)?;
+                self.send_cmd(Command::DisplayDutySet, 0)
+            }
             1..=15 => {
-                self.vd_on()?;
-                self.send_cmd(Command::DisplayDutySet, brightness)
+                self.send_cmd(Command::DisplayDutySet, brightness)?;
+                self.brightness = brightness;
+                if self.lights == Lights::Off {
+                    self.set_lights(Lights::Normal)?;
+                }
+                Ok(())
             }
             _ => Err(Error::InvalidInput),
         }
     }
 
-    /// Send one command byte with with four bits argument payload
+    /// Put the display into standby
     ///
-    /// (The higher four bit specify the command, the lower four bit are the argument)
-    fn send_cmd(&mut self, cmd: Command, arg: u8) -> Result<(), Error> {
-        let arg = arg & 0x0F;
-        let command = [cmd as u8 | arg];
-        self.cs.set_low().map_err(|_| Error::Gpio)?;
-        self.delay.delay_us(5);
-        self.spi.write(&command).map_err(|_| Error::Spi)?;
-        self.delay.delay_us(20);
-        self.cs.set_high().map_err(|_| Error::Gpio)?;
+    /// Blanks the display and drops the duty cycle to the minimum.
+    /// With `cut_supply` the supply voltage is switched off as well (if supply pin is configured).
+    ///
+    /// The display RAM is kept by the controller, [wake()](Self::wake()) restores the previous content and brightness.
+    pub fn standby(&mut self, cut_supply: bool) -> Result<(), Error> {
+        self.send_cmd(Command::Lights, Lights::Off as u8)?;
+        self.lights = Lights::Off;
+        self.send_cmd(Command::DisplayDutySet, 0)?;
+        if cut_supply {
+            self.supply(false)?;
+        }
+        Ok(())
+    }
+
+    /// Wake the display from standby
+    ///
+    /// Turns the supply voltage on and restores the brightness from before [standby()](Self::standby()).
+    pub fn wake(&mut self) -> Result<(), Error> {
+        self.supply(true)?;
+        self.send_cmd(Command::DisplayDutySet, self.brightness)?;
+        self.send_cmd(Command::Lights, Lights::Normal as u8)?;
+        self.lights = Lights::Normal;
+        Ok(())
+    }
+
+    /// Turn all segments off, keeping the supply voltage and the display RAM
+    ///
+    /// Unlike [vd_off()](Self::vd_off()) the filament stays powered, so [unblank()](Self::unblank())
+    /// shows the content immediately without a visible warm-up. The duty cycle is kept, unlike with [brightness(0)](Self::brightness()).
+    pub fn blank(&mut self) -> Result<(), Error> {
+        self.set_lights(Lights::Off)
+    }
+
+    /// Show the display RAM content again after [blank()](Self::blank())
+    pub fn unblank(&mut self) -> Result<(), Error> {
+        self.set_lights(Lights::Normal)
+    }
+
+    /// Switch all segments off, on or back to the display RAM content
+    pub fn set_lights(&mut self, lights: Lights) -> Result<(), Error> {
+        self.send_cmd(Command::Lights, lights as u8)?;
+        self.lights = lights;
         Ok(())
     }
 
+    /// Set the number of scanned digits (`1..=16`)
+    ///
+    /// The driver keeps addressing `N` digits, [reapply_settings()](Self::reapply_settings()) sets `N` again.
+    pub fn set_num_digits(&mut self, digits: u8) -> Result<(), Error> {
+        if !(1..=16).contains(&digits) {
+            return Err(Error::InvalidInput);
+        }
+        // 16 digits are encoded as 0
+        self.send_cmd(Command::NumDigitsSet, digits)
+    }
+
+    /// Send a single byte command with a four bit argument
+    ///
+    /// The argument is checked against the range of the command: `0..=15` for duty and digit count, `0..=2` for
+    /// the [Lights] mode. RAM writes carry data and are rejected, they are sent with [write_buf()](Self::write_buf()).
+    /// [Lights] and duty commands update the tracked state: the duty is set immediately like with
+    /// [brightness()](Self::brightness()) without ramp, so `0` blanks the display and keeps the brightness.
+    /// The digit count is restored by [reapply_settings()](Self::reapply_settings()).
+    pub fn send(&mut self, command: Command, arg: u8) -> Result<(), Error> {
+        match (command, arg) {
+            (Command::Lights, 0) => self.set_lights(Lights::Normal),
+            (Command::Lights, 1) => self.set_lights(Lights::Off),
+            (Command::Lights, 2) => self.set_lights(Lights::On),
+            (Command::DisplayDutySet, 0..=15) => self.set_duty(arg),
+            (Command::NumDigitsSet, 0..=15) => self.send_cmd(command, arg),
+            _ => Err(Error::InvalidInput),
+        }
+    }
+
+    /// Send the digit count, brightness and lights setting again
+    ///
+    /// Restores the configuration after the controller lost its state, e.g. because of a brown out.
+    /// To restore the display content as well use [BufferedHCS12SS59T::reapply()](framebuffer::BufferedHCS12SS59T::reapply()).
+    pub fn reapply_settings(&mut self) -> Result<(), Error> {
+        // 16 digits are encoded as 0
+        self.send_cmd(Command::NumDigitsSet, N as u8)?;
+        self.send_cmd(Command::DisplayDutySet, self.brightness)?;
+        self.send_cmd(Command::Lights, self.lights as u8)
+    }
+
     /// Write abritrary bytes to the display controller
+    ///
+    /// Without a dedicated CS-Pin at most 17 bytes (command and full DCRAM) can be written at once,
+    /// unless the byte gap is dropped with [Timing::for_spi_clock()].
     pub fn write_buf(&mut self, buf: &[u8]) -> Result<(), Error> {
-        self.cs.set_low().map_err(|_| Error::Gpio)?;
-        self.delay.delay_us(1);
-        for byte in buf {
-            self.spi.write(&[*byte]).map_err(|_| Error::Spi)?;
-            self.delay.delay_us(8);
-        }
-        self.delay.delay_us(12);
-        self.cs.set_high().map_err(|_| Error::Gpio)?;
-        Ok(())
+        self.transfer(
+            buf,
+            self.timing.setup,
+            self.timing.byte_gap,
+            self.timing.hold,
+        )
+    }
+
+    /// Write bytes streamed from an iterator to the display controller, e.g. from an encoder
+    ///
+    /// Same timing as [write_buf()](Self::write_buf()), interfaces writing byte by byte consume the iterator
+    /// while sending. Otherwise the frame is collected first and limited to 33 bytes (command and full CGRAM).
+    /// With the `trace` feature only the command byte is logged.
+    pub fn write_buf_iter<I: IntoIterator<Item = u8>>(&mut self, buf: I) -> Result<(), Error> {
+        let timing = FrameTiming {
+            setup: self.timing.setup,
+            gap: self.timing.byte_gap,
+            hold: self.timing.hold,
+        };
+        let buf = buf.into_iter();
+        #[cfg(feature = "trace")]
+        let mut buf = buf.peekable();
+        #[cfg(feature = "trace")]
+        if let Some(command) = buf.peek() {
+            trace_frame(&[*command]);
+        }
+        #[cfg(feature = "critical-section")]
+        return critical_section::with(|_| {
+            self.interface
+                .write_frame_iter(buf, timing, &mut self.delay)
+        });
+        #[cfg(not(feature = "critical-section"))]
+        self.interface
+            .write_frame_iter(buf, timing, &mut self.delay)
     }
 
     /// Write a ASCII string to the display RAM.
     ///
     /// Characters are mapped using the internal font map.
-    /// Strings are truncated to fit the display.
+    /// Strings are truncated to fit the display, shorter strings are padded with the fill character (space by default).
     pub fn display<T>(&mut self, text: T) -> Result<(), Error>
     where
         T: IntoIterator,
         T::Item: Into<FontTable>,
     {
-        let mut data = [48_u8; NUM_DIGITS + 1];
+        let mut data = [self.fill as u8; DCRAM_SIZE + 1];
         data[0] = Command::DCRamWrite as u8;
 
-        for (data, c) in data.iter_mut().skip(1).rev().zip(text.into_iter()) {
+        for (data, c) in data[1..=N].iter_mut().rev().zip(text) {
             *data = c.into() as u8;
         }
-        self.cs.set_low().map_err(|_| Error::Gpio)?;
-        self.delay.delay_us(1);
-        for byte in data {
-            self.spi.write(&[byte]).map_err(|_| Error::Spi)?;
-            self.delay.delay_us(8);
+        self.write_buf(&data[..=N])
+    }
+
+    /// Show a composed [DisplayBuffer] with a single write
+    pub fn display_buffer(&mut self, buffer: &DisplayBuffer<N>) -> Result<(), Error> {
+        self.display(*buffer)
+    }
+
+    /// Write a string to the display RAM.
+    ///
+    /// Same as [display()](Self::display()), but characters are mapped with the [CharMapper] of the driver.
+    pub fn display_str(&mut self, text: &str) -> Result<(), Error> {
+        let mapper = self.mapper;
+        self.display(text.chars().map(|c| mapper.map(c)))
+    }
+
+    /// Write a string to the display RAM, failing on characters which are not in the font table
+    ///
+    /// Unlike [display_str()](Self::display_str()) characters are not replaced by `?`, but rejected with
+    /// [StrictError::InvalidChar] and the index of the first offending character. The whole text is checked,
+    /// also the characters beyond the last digit which would be cut off. Uses the built-in conversion,
+    /// see [FontTable::try_from_char()], the [CharMapper] is not used.
+    pub fn display_str_strict(&mut self, text: &str) -> Result<(), StrictError> {
+        if let Some(index) = text
+            .chars()
+            .position(|c| FontTable::try_from_char(c).is_none())
+        {
+            return Err(StrictError::InvalidChar { index });
+        }
+        Ok(self.display(text.chars())?)
+    }
+
+    /// Write ASCII bytes to the display RAM, e.g. straight from a UART buffer
+    ///
+    /// Bytes are converted with [FontTable::from_ascii()] without UTF-8 validation, the [CharMapper] is not used.
+    pub fn display_ascii(&mut self, text: &[u8]) -> Result<(), Error> {
+        self.display(text.iter().map(|b| FontTable::from_ascii(*b)))
+    }
+
+    /// Display formatted text, e.g. `display_fmt(format_args!("T {}C", temp))`
+    ///
+    /// The text is formatted directly into the command buffer without an intermediate string,
+    /// characters beyond the last digit are dropped and formatting is stopped early.
+    pub fn display_fmt(&mut self, args: core::fmt::Arguments<'_>) -> Result<(), Error> {
+        let mut data = [self.fill as u8; DCRAM_SIZE + 1];
+        data[0] = Command::DCRamWrite as u8;
+        let mut writer = format::CellWriter {
+            cells: &mut data[1..=N],
+            pos: 0,
+            mapper: self.mapper,
+        };
+        // An error only signals that the display is full
+        let _ = core::fmt::write(&mut writer, args);
+        self.write_buf(&data[..=N])
+    }
+
+    /// Display a signed integer
+    ///
+    /// Numbers wider than the display are truncated on the right.
+    pub fn display_int(&mut self, value: i32, format: format::IntFormat) -> Result<(), Error> {
+        let text = format::format_int(value < 0, value.unsigned_abs(), format);
+        self.display_aligned(text.as_str(), format.align, self.fill)
+    }
+
+    /// Display an unsigned integer
+    ///
+    /// See [display_int()](Self::display_int()).
+    pub fn display_uint(&mut self, value: u32, format: format::IntFormat) -> Result<(), Error> {
+        let text = format::format_int(false, value, format);
+        self.display_aligned(text.as_str(), format.align, self.fill)
+    }
+
+    /// Display a fixed-point number with `decimals` places
+    ///
+    /// `value` is scaled by `10^decimals`, e.g. `display_fixed(-125, 1, ..)` shows `-12.5`.
+    /// At most 9 decimal places are supported.
+    pub fn display_fixed(
+        &mut self,
+        value: i32,
+        decimals: u8,
+        format: format::FixedFormat,
+    ) -> Result<(), Error> {
+        let text = format::format_fixed(
+            value < 0,
+            value.unsigned_abs(),
+            decimals,
+            format.separator,
+            format.int,
+        );
+        self.display_aligned(text.as_str(), format.int.align, self.fill)
+    }
+
+    /// Display a float rounded to `decimals` places
+    ///
+    /// See [display_fixed()](Self::display_fixed()), values beyond the `i32` range after scaling saturate.
+    pub fn display_f32(
+        &mut self,
+        value: f32,
+        decimals: u8,
+        format: format::FixedFormat,
+    ) -> Result<(), Error> {
+        self.display_fixed(format::f32_to_fixed(value, decimals), decimals, format)
+    }
+
+    /// Display a number in hexadecimal
+    ///
+    /// Digits `A` to `F` are shown uppercase, e.g. `0xBEEF` with [RadixFormat::prefix](format::RadixFormat::prefix).
+    pub fn display_hex(&mut self, value: u32, format: format::RadixFormat) -> Result<(), Error> {
+        let text = format::format_radix(value, 4, "0x", format);
+        self.display_aligned(text.as_str(), format.align, self.fill)
+    }
+
+    /// Display a number in binary
+    ///
+    /// All 16 digits only fit on the display without prefix and grouping.
+    pub fn display_bin(&mut self, value: u16, format: format::RadixFormat) -> Result<(), Error> {
+        let text = format::format_radix(value.into(), 1, "0b", format);
+        self.display_aligned(text.as_str(), format.align, self.fill)
+    }
+
+    /// Write a string with the given alignment
+    ///
+    /// Unused digits on both sides are filled with `fill`, characters are mapped like in [display_str()](Self::display_str()).
+    pub fn display_aligned<C: Into<FontTable>>(
+        &mut self,
+        text: &str,
+        align: format::Alignment,
+        fill: C,
+    ) -> Result<(), Error> {
+        let fill = fill.into();
+        let padding = align.padding(text.chars().count(), N);
+        let mapper = self.mapper;
+        let text = text.chars().map(|c| mapper.map(c));
+        let padded = core::iter::repeat_n(fill, padding)
+            .chain(text)
+            .chain(core::iter::repeat(fill));
+        self.display(padded.take(N))
+    }
+
+    /// Write a run of font codes to consecutive DCRAM addresses starting at `addr`.
+    ///
+    /// The controller increments the DCRAM address after every byte.
+    pub(crate) fn write_dcram(&mut self, addr: u8, codes: &[u8]) -> Result<(), Error> {
+        let addr = addr & 0x0F;
+        if codes.len() > DCRAM_SIZE - addr as usize {
+            return Err(Error::InvalidInput);
+        }
+        let mut data = [0_u8; DCRAM_SIZE + 1];
+        data[0] = Command::DCRamWrite as u8 | addr;
+        data[1..=codes.len()].copy_from_slice(codes);
+        self.write_buf(&data[..=codes.len()])
+    }
+
+    /// Display text like [display()](Self::display()), but only send the characters that differ from `shown`
+    ///
+    /// `shown` is the DCRAM content of the last call, `None` sends the whole text. It is updated on success,
+    /// on errors it is reset to `None` since the content of the display is unknown.
+    pub(crate) fn display_changes<T>(
+        &mut self,
+        shown: &mut Option<[u8; N]>,
+        text: T,
+    ) -> Result<(), Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        let mut frame = [self.fill as u8; N];
+        for (code, c) in frame.iter_mut().rev().zip(text) {
+            *code = c.into() as u8;
+        }
+        let result = match shown.take() {
+            Some(old) => self.write_dcram_changes(&old, &frame),
+            None => self.write_dcram(0, &frame),
+        };
+        if result.is_ok() {
+            *shown = Some(frame);
+        }
+        result
+    }
+
+    fn write_dcram_changes(&mut self, old: &[u8; N], frame: &[u8; N]) -> Result<(), Error> {
+        let mut changed = (0..N)
+            .filter(|addr| old[*addr] != frame[*addr])
+            .fold(0_u16, |acc, addr| acc | 1 << addr);
+        // Every run of changed characters costs a command byte, rewriting everything at once is cheaper beyond that
+        let runs = (changed & !(changed << 1)).count_ones();
+        if runs + changed.count_ones() > N as u32 {
+            return self.write_dcram(0, frame);
+        }
+        while changed != 0 {
+            let start = changed.trailing_zeros() as usize;
+            let len = (changed >> start).trailing_ones() as usize;
+            self.write_dcram(start as u8, &frame[start..start + len])?;
+            changed &= !((((1_u32 << len) - 1) << start) as u16);
         }
-        self.delay.delay_us(12);
-        self.cs.set_high().map_err(|_| Error::Gpio)?;
         Ok(())
     }
 
     /// Write a single character to display RAM.
     ///
-    /// The HCS-12SS59T has 16 byte DCRAM, from which 0..12 are usable for the 12 connected digits.
+    /// The HCS-12SS59T has 16 byte DCRAM, from which 0..N are usable for the N connected digits.
     pub fn set_char<C: Into<FontTable>>(&mut self, addr: u8, char: C) -> Result<(), Error> {
         let addr = addr & 0x0F;
         let command = [Command::DCRamWrite as u8 | addr, char.into() as u8];
+        self.write_buf(&command)
+    }
 
-        self.cs.set_low().map_err(|_| Error::Gpio)?;
-        self.delay.delay_us(1);
-        for byte in command {
-            self.spi.write(&[byte]).map_err(|_| Error::Spi)?;
-            self.delay.delay_us(8);
-        }
-        self.delay.delay_us(12);
-        self.cs.set_high().map_err(|_| Error::Gpio)?;
-        Ok(())
+    /// Write the auxiliary segment bits of one digit to ADRAM
+    ///
+    /// Like DCRAM, the ADRAM has 16 addresses from which 0..N are usable.
+    pub fn set_adram<A: Into<AdramBits>>(&mut self, addr: u8, value: A) -> Result<(), Error> {
+        let addr = addr & 0x0F;
+        let command = [Command::ADRamWrite as u8 | addr, value.into().bits()];
+        self.write_buf(&command)
+    }
+
+    /// Async version of [set_adram()](Self::set_adram()), written with an async SPI device
+    ///
+    /// An escape hatch for boards without CS-Pin: the frame is written directly to `spi`, the interface of the
+    /// driver is bypassed, including its CS-Pin and wrappers like [Retry](interface::Retry) or
+    /// [TimingCheck](interface::TimingCheck). The SPI device has to select the chip, the timing is met by delay
+    /// operations inside of the transaction like with a [SpiInterface] without CS-Pin.
+    #[cfg(feature = "async")]
+    pub async fn set_adram_async<S, A>(
+        &mut self,
+        spi: &mut S,
+        addr: u8,
+        value: A,
+    ) -> Result<(), Error>
+    where
+        S: embedded_hal_async::spi::SpiDevice,
+        A: Into<AdramBits>,
+    {
+        use embedded_hal::spi::Operation;
+
+        let addr = addr & 0x0F;
+        let command = [Command::ADRamWrite as u8 | addr, value.into().bits()];
+        #[cfg(feature = "trace")]
+        trace_frame(&command);
+        spi.transaction(&mut [
+            Operation::DelayNs(self.timing.setup.saturating_mul(1000)),
+            Operation::Write(&command[..1]),
+            Operation::DelayNs(self.timing.byte_gap.saturating_mul(1000)),
+            Operation::Write(&command[1..]),
+            Operation::DelayNs(self.timing.hold.saturating_mul(1000)),
+        ])
+        .await
+        .map_err(|_| Error::Spi)
     }
 
     /// Set character generator RAM
@@ -225,7 +1242,14 @@ where
     ///   S     3     2
     ///   SEG6     SEG5
     /// ```
-    pub fn set_cgram_pattern(&mut self, addr: FontTable, pattern: [u8; 2]) -> Result<(), Error> {
+    ///
+    /// Patterns can be built with [Glyph](glyph::Glyph) instead of raw bytes.
+    pub fn set_cgram_pattern<P: Into<[u8; 2]>>(
+        &mut self,
+        addr: FontTable,
+        pattern: P,
+    ) -> Result<(), Error> {
+        let pattern = pattern.into();
         use FontTable::*;
         if !matches!(
             addr,
@@ -252,15 +1276,132 @@ where
             pattern[0],
             pattern[1],
         ];
+        self.write_buf(&command)
+    }
+
+    /// Write several CGRAM patterns, e.g. from the [glyphs] library
+    pub fn load_glyphs(&mut self, glyphs: &[(FontTable, glyph::Glyph)]) -> Result<(), Error> {
+        glyphs
+            .iter()
+            .try_for_each(|(addr, glyph)| self.set_cgram_pattern(*addr, *glyph))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::vec::Vec;
 
-        self.cs.set_low().map_err(|_| Error::Gpio)?;
-        self.delay.delay_us(1);
-        for byte in command {
-            self.spi.write(&[byte]).map_err(|_| Error::Spi)?;
-            self.delay.delay_us(8);
+    use super::*;
+
+    /// Records every written frame
+    #[derive(Default)]
+    struct Frames(Vec<Vec<u8>>);
+
+    impl Interface for Frames {
+        fn write_frame<D: DelayNs>(
+            &mut self,
+            frame: &[u8],
+            _timing: FrameTiming,
+            _delay: &mut D,
+        ) -> Result<(), Error> {
+            self.0.push(frame.to_vec());
+            Ok(())
         }
-        self.delay.delay_us(12);
-        self.cs.set_high().map_err(|_| Error::Gpio)?;
-        Ok(())
+    }
+
+    /// Output pin whose level stays observable after it was moved into the driver
+    #[derive(Clone)]
+    struct Pin(Rc<Cell<bool>>);
+
+    impl Pin {
+        fn high() -> Self {
+            Self(Rc::new(Cell::new(true)))
+        }
+
+        fn is_high(&self) -> bool {
+            self.0.get()
+        }
+    }
+
+    impl ErrorType for Pin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for Pin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.set(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.set(true);
+            Ok(())
+        }
+    }
+
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    /// Initializes with [init_start()](HCS12SS59T::init_start()) and polls every millisecond until ready
+    fn init_polled(
+        n_reset: Option<Pin>,
+        n_vdon: Pin,
+    ) -> HCS12SS59T<Frames, Pin, Pin, NoDelay, NUM_DIGITS> {
+        let mut vfd = HCS12SS59T::builder()
+            .vdon(n_vdon)
+            .build_with_interface(Frames::default(), n_reset, NoDelay)
+            .init_start(0)
+            .unwrap();
+        let mut now_us = 0;
+        while let Err(nb::Error::WouldBlock) = vfd.init_poll(now_us) {
+            now_us += 1000;
+            assert!(now_us < 100_000, "initialization did not finish");
+        }
+        vfd.into_ready().ok().unwrap()
+    }
+
+    fn init_frames() -> Vec<Vec<u8>> {
+        std::vec![std::vec![0x6C], std::vec![0x57], std::vec![0x70]]
+    }
+
+    #[test]
+    fn init_poll_with_reset_pin() {
+        let (n_reset, n_vdon) = (Pin::high(), Pin::high());
+        let vfd = init_polled(Some(n_reset.clone()), n_vdon.clone());
+
+        assert!(n_reset.is_high(), "reset released");
+        assert!(!n_vdon.is_high(), "supply on");
+        assert!(vfd.is_supply_on());
+        assert_eq!(vfd.destroy().0 .0, init_frames());
+    }
+
+    #[test]
+    fn init_poll_without_reset_pin() {
+        let n_vdon = Pin::high();
+        let vfd = init_polled(None, n_vdon.clone());
+
+        assert!(!n_vdon.is_high(), "supply on");
+        assert!(vfd.is_supply_on());
+        assert_eq!(vfd.destroy().0 .0, init_frames());
+    }
+
+    #[test]
+    fn init_poll_matches_init() {
+        let n_vdon = Pin::high();
+        let vfd = HCS12SS59T::builder()
+            .vdon(n_vdon.clone())
+            .build_with_interface(Frames::default(), None::<Pin>, NoDelay)
+            .init()
+            .unwrap();
+
+        assert!(!n_vdon.is_high(), "supply on");
+        assert_eq!(vfd.destroy().0 .0, init_frames());
     }
 }
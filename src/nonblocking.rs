@@ -0,0 +1,156 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::interface::{FrameTiming, StepInterface, MAX_FRAME};
+use super::{Command, Error, FontTable, DCRAM_SIZE, HCS12SS59T};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Select,
+    Setup,
+    Byte,
+    Gap,
+    Hold,
+    Done,
+}
+
+/// Frame written by repeated [poll()](HCS12SS59T::poll()) calls
+///
+/// Created by the `_nb` methods of the driver, e.g. [display_str_nb()](HCS12SS59T::display_str_nb()).
+/// The frame is copied, so the driver can be used for other things between the polls.
+/// Needs an interface which can write step by step, e.g. a [SpiInterface](crate::interface::SpiInterface) with CS-Pin.
+///
+/// ``` text
+/// let mut write = vfd.display_str_nb("HELLO")?;
+/// loop {
+///     match vfd.poll(&mut write, timer.now_us()) {
+///         Ok(()) => break,
+///         Err(nb::Error::WouldBlock) => poll_other_tasks(),
+///         Err(nb::Error::Other(e)) => return Err(e),
+///     }
+/// }
+/// ```
+pub struct PendingWrite {
+    buf: [u8; MAX_FRAME],
+    len: usize,
+    pos: usize,
+    timing: FrameTiming,
+    step: Step,
+    since: u32,
+}
+
+impl PendingWrite {
+    fn new(frame: &[u8], timing: FrameTiming) -> Result<Self, Error> {
+        let mut buf = [0; MAX_FRAME];
+        buf.get_mut(..frame.len())
+            .ok_or(Error::InvalidInput)?
+            .copy_from_slice(frame);
+        #[cfg(feature = "trace")]
+        super::trace_frame(frame);
+        Ok(Self {
+            buf,
+            len: frame.len(),
+            pos: 0,
+            timing,
+            step: Step::Select,
+            since: 0,
+        })
+    }
+
+    /// Returns wether the frame was written completely
+    pub fn is_done(&self) -> bool {
+        self.step == Step::Done
+    }
+
+    fn advance<IF: StepInterface, D: DelayNs>(
+        &mut self,
+        interface: &mut IF,
+        delay: &mut D,
+        now_us: u32,
+    ) -> nb::Result<(), Error> {
+        loop {
+            let elapsed = now_us.wrapping_sub(self.since);
+            match self.step {
+                Step::Select => {
+                    interface.select(true)?;
+                    self.since = now_us;
+                    self.step = Step::Setup;
+                }
+                Step::Setup if elapsed >= self.timing.setup => self.step = Step::Byte,
+                Step::Byte if self.pos < self.len => {
+                    interface.write_byte(self.buf[self.pos], delay)?;
+                    self.pos += 1;
+                    self.since = now_us;
+                    self.step = Step::Gap;
+                }
+                Step::Byte => {
+                    self.since = now_us;
+                    self.step = Step::Hold;
+                }
+                Step::Gap if elapsed >= self.timing.gap => self.step = Step::Byte,
+                Step::Hold if elapsed >= self.timing.hold => {
+                    interface.select(false)?;
+                    self.step = Step::Done;
+                }
+                Step::Done => return Ok(()),
+                _ => return Err(nb::Error::WouldBlock),
+            }
+        }
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: StepInterface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Advance a [PendingWrite] without blocking delays
+    ///
+    /// `now_us` is a free running microsecond timestamp, which is allowed to wrap around.
+    /// Every call writes as far as the timing allows and returns [WouldBlock](nb::Error::WouldBlock)
+    /// until the frame is complete. After an error CS is released and the next call starts the frame again.
+    pub fn poll(&mut self, write: &mut PendingWrite, now_us: u32) -> nb::Result<(), Error> {
+        let result = write.advance(&mut self.interface, &mut self.delay, now_us);
+        if let Err(nb::Error::Other(_)) = result {
+            write.step = Step::Select;
+            write.pos = 0;
+            // The original error is more useful than a failing release
+            self.interface.select(false).ok();
+        }
+        result
+    }
+
+    /// Prepare writing abritrary bytes, see [write_buf()](Self::write_buf())
+    ///
+    /// At most 33 bytes (command and full CGRAM) can be written at once.
+    pub fn write_buf_nb(&mut self, buf: &[u8]) -> Result<PendingWrite, Error> {
+        let timing = FrameTiming {
+            setup: self.timing.setup,
+            gap: self.timing.byte_gap,
+            hold: self.timing.hold,
+        };
+        PendingWrite::new(buf, timing)
+    }
+
+    /// Prepare writing text to the display RAM, see [display()](Self::display())
+    pub fn display_nb<T>(&mut self, text: T) -> Result<PendingWrite, Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        let mut data = [self.fill as u8; DCRAM_SIZE + 1];
+        data[0] = Command::DCRamWrite as u8;
+        for (data, c) in data[1..=N].iter_mut().rev().zip(text) {
+            *data = c.into() as u8;
+        }
+        self.write_buf_nb(&data[..=N])
+    }
+
+    /// Prepare writing a string to the display RAM, see [display_str()](Self::display_str())
+    pub fn display_str_nb(&mut self, text: &str) -> Result<PendingWrite, Error> {
+        let mapper = self.mapper;
+        self.display_nb(text.chars().map(|c| mapper.map(c)))
+    }
+}
@@ -0,0 +1,309 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::animation::Animation;
+use super::format::{self, Alignment, IntFormat};
+use super::framebuffer::BufferedHCS12SS59T;
+use super::interface::Interface;
+use super::{Error, FontTable};
+
+/// Window of consecutive digits
+///
+/// Positions count from the leftmost digit, e.g. `Region::new(0, 8)` for a clock on the left
+/// and `Region::new(8, 4)` for a temperature on the right of a 12 digit display.
+/// Write to it with [BufferedHCS12SS59T::region()], all regions are composited in the buffer
+/// and sent together on [flush()](BufferedHCS12SS59T::flush()).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Region {
+    start: usize,
+    width: usize,
+}
+
+impl Region {
+    /// Region of `width` digits, starting at position `start`
+    pub const fn new(start: usize, width: usize) -> Self {
+        Self { start, width }
+    }
+
+    /// Position of the leftmost digit of the region
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Number of digits of the region
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Split into two regions, the left one with `width` digits
+    ///
+    /// `width` is clamped to the width of the region.
+    pub const fn split_at(self, width: usize) -> (Region, Region) {
+        let width = if width < self.width {
+            width
+        } else {
+            self.width
+        };
+        (
+            Region::new(self.start, width),
+            Region::new(self.start + width, self.width - width),
+        )
+    }
+}
+
+/// Writer confined to a [Region] of a buffered driver
+///
+/// Text is truncated to the region and shorter text is padded with the fill character, digits outside of the region are never touched.
+pub struct RegionWriter<'a, IF, RstPin, VdonPin, Delay, const N: usize> {
+    buffer: &'a mut BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    region: Region,
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Writer for the digits of `region`
+    ///
+    /// Returns [Error::InvalidInput] if the region exceeds the display.
+    pub fn region(
+        &mut self,
+        region: Region,
+    ) -> Result<RegionWriter<'_, IF, RstPin, VdonPin, Delay, N>, Error> {
+        if region.start + region.width > N {
+            return Err(Error::InvalidInput);
+        }
+        Ok(RegionWriter {
+            buffer: self,
+            region,
+        })
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> RegionWriter<'_, IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// The region written to
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    fn set(&mut self, pos: usize, c: FontTable) {
+        // Position is always inside of the display, checked by region()
+        let _ = self
+            .buffer
+            .set_char((N - 1 - self.region.start - pos) as u8, c);
+    }
+
+    /// Write text to the region, see [BufferedHCS12SS59T::display()]
+    pub fn display<T>(&mut self, text: T)
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        let mut text = text.into_iter();
+        for pos in 0..self.region.width {
+            let c = text.next().map_or(self.buffer.fill(), Into::into);
+            self.set(pos, c);
+        }
+    }
+
+    /// Write a string to the region, see [BufferedHCS12SS59T::display_str()]
+    pub fn display_str(&mut self, text: &str) {
+        self.display_aligned(text, Alignment::Left, self.buffer.fill());
+    }
+
+    /// Write formatted text to the region, see [BufferedHCS12SS59T::display_fmt()]
+    pub fn display_fmt(&mut self, args: core::fmt::Arguments<'_>) {
+        let mut text = format::Formatted::new();
+        // Writing to Formatted never fails, it truncates
+        let _ = core::fmt::write(&mut text, args);
+        self.display_str(text.as_str());
+    }
+
+    /// Write a signed integer aligned inside of the region, see [HCS12SS59T::display_int()](crate::HCS12SS59T::display_int())
+    pub fn display_int(&mut self, value: i32, format: IntFormat) {
+        let text = format::format_int(value < 0, value.unsigned_abs(), format);
+        self.display_aligned(text.as_str(), format.align, self.buffer.fill());
+    }
+
+    /// Write an unsigned integer aligned inside of the region, see [HCS12SS59T::display_uint()](crate::HCS12SS59T::display_uint())
+    pub fn display_uint(&mut self, value: u32, format: IntFormat) {
+        let text = format::format_int(false, value, format);
+        self.display_aligned(text.as_str(), format.align, self.buffer.fill());
+    }
+
+    /// Write a string with the given alignment inside of the region
+    ///
+    /// Characters are rendered like with [BufferedHCS12SS59T::display_str()], unused digits are filled with `fill`.
+    pub fn display_aligned<C: Into<FontTable>>(&mut self, text: &str, align: Alignment, fill: C) {
+        let fill = fill.into();
+        let padding = align.padding(self.buffer.cell_count(text), self.region.width);
+        let mut text = text.chars().peekable();
+        for pos in 0..self.region.width {
+            let c = if pos < padding {
+                fill
+            } else {
+                self.buffer.next_cell(&mut text).unwrap_or(fill)
+            };
+            self.set(pos, c);
+        }
+    }
+
+    /// Show the next frame of an animation in the region
+    ///
+    /// Create the animation with the width of the region, e.g. `ScrollingText::<Cycle, 4>::new_with_digits()`.
+    pub fn animate<A: Animation>(&mut self, animation: &mut A) {
+        self.display(animation.next_frame());
+    }
+
+    /// Fill the region with blanks
+    pub fn clear(&mut self) {
+        self.display(core::iter::empty::<FontTable>());
+    }
+}
+
+/// Field of a [Layout]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Field {
+    region: Region,
+    align: Alignment,
+    name: &'static str,
+}
+
+impl Field {
+    /// Field of `width` digits starting at position `start`, its content is aligned with `align`
+    pub const fn new(start: usize, width: usize, align: Alignment) -> Self {
+        Self {
+            region: Region::new(start, width),
+            align,
+            name: "",
+        }
+    }
+
+    /// Set a name to look up the field with [Layout::handle()]
+    pub const fn named(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Digits covered by the field
+    pub const fn region(&self) -> Region {
+        self.region
+    }
+}
+
+/// Fixed arrangement of fields, e.g. for instrument panels
+///
+/// Fields are updated by their handle (the index in the field list) and rendered into the buffered driver,
+/// [flush()](BufferedHCS12SS59T::flush()) then only transmits what changed.
+///
+/// ``` text
+/// const FIELDS: &[Field] = &[
+///     Field::new(0, 5, Alignment::Left).named("speed"),
+///     Field::new(8, 4, Alignment::Right).named("temp"),
+/// ];
+/// let layout = Layout::new(FIELDS);
+/// layout.set_int(&mut vfd, 1, temp, IntFormat::default())?;
+/// vfd.flush()?;
+/// ```
+pub struct Layout<'a> {
+    fields: &'a [Field],
+}
+
+impl<'a> Layout<'a> {
+    /// Layout of `fields`, the handle of a field is its index
+    pub const fn new(fields: &'a [Field]) -> Self {
+        Self { fields }
+    }
+
+    /// Handle of the field called `name`
+    pub fn handle(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field.name == name)
+    }
+
+    /// Render a string into a field
+    ///
+    /// Returns [Error::InvalidInput] for unknown handles or fields exceeding the display.
+    pub fn set_str<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &self,
+        vfd: &mut BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+        field: usize,
+        text: &str,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let field = self.fields.get(field).ok_or(Error::InvalidInput)?;
+        let fill = vfd.fill();
+        vfd.region(field.region)?
+            .display_aligned(text, field.align, fill);
+        Ok(())
+    }
+
+    /// Render formatted text into a field, see [set_str()](Self::set_str())
+    pub fn set_fmt<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &self,
+        vfd: &mut BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+        field: usize,
+        args: core::fmt::Arguments<'_>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let mut text = format::Formatted::new();
+        // Writing to Formatted never fails, it truncates
+        let _ = core::fmt::write(&mut text, args);
+        self.set_str(vfd, field, text.as_str())
+    }
+
+    /// Render a signed integer into a field
+    ///
+    /// The alignment of the field replaces the alignment of `format`.
+    pub fn set_int<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &self,
+        vfd: &mut BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+        field: usize,
+        value: i32,
+        format: IntFormat,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let text = format::format_int(value < 0, value.unsigned_abs(), format);
+        self.set_str(vfd, field, text.as_str())
+    }
+
+    /// Blank all fields
+    pub fn clear<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &self,
+        vfd: &mut BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        (0..self.fields.len()).try_for_each(|field| self.set_str(vfd, field, ""))
+    }
+}
@@ -0,0 +1,644 @@
+//! Display simulators for the host
+//!
+//! [TerminalVfd] is an [Interface] which decodes the commands like the controller
+//! and draws the digits with box-drawing characters to a terminal:
+//!
+//! ``` text
+//! let mut vfd = HCS12SS59T::builder()
+//!     .build_with_interface(TerminalVfd::stdout(), None::<NoPin>, StdDelay)
+//!     .init()?;
+//! vfd.display_str("HELLO WORLD")?;
+//! ```
+//!
+//! With the `embedded-graphics` feature, `GraphicsVfd` draws the segments into any `DrawTarget`,
+//! e.g. the `SimulatorDisplay` of [embedded-graphics-simulator](https://docs.rs/embedded-graphics-simulator)
+//! shown in a window:
+//!
+//! ``` text
+//! let display = SimulatorDisplay::<Rgb888>::new(GraphicsVfd::<(), Rgb888>::size(12));
+//! let mut vfd = HCS12SS59T::builder()
+//!     .build_with_interface(GraphicsVfd::new(display, Rgb888::CSS_AQUAMARINE, Rgb888::new(0, 24, 24)), None::<NoPin>, StdDelay)
+//!     .init()?;
+//! let mut window = Window::new("HCS-12SS59T", &OutputSettings::default());
+//! vfd.display_str("HELLO WORLD")?;
+//! window.update(vfd.interface().target());
+//! ```
+//!
+//! [Recorder] logs the shown frames and custom characters instead, to compare whole animations in host tests:
+//!
+//! ``` text
+//! let mut vfd = HCS12SS59T::builder()
+//!     .build_with_interface(Recorder::new(), None::<NoPin>, StdDelay)
+//!     .init()?;
+//! vfd.display_str("HELLO")?;
+//! let (recorder, ..) = vfd.destroy();
+//! assert!(recorder.frames().count() == 1);
+//! ```
+
+use std::io::{self, Write};
+use std::string::String;
+use std::vec::Vec;
+
+#[cfg(feature = "embedded-graphics")]
+use embedded_graphics::prelude::*;
+#[cfg(feature = "embedded-graphics")]
+use embedded_graphics::primitives::{Line, PrimitiveStyle};
+use embedded_hal::delay::DelayNs;
+
+use super::glyph::Segments;
+use super::host::StdDelay;
+use super::interface::{FrameTiming, Interface};
+use super::{
+    glyphs, CharMapper, Command, DefaultMapper, Error, FontTable, Lights, SegmentTextDisplay,
+    CGRAM_SLOTS, DCRAM_SIZE, NUM_DIGITS,
+};
+
+/// Memory and settings of the controller, updated from the written frames
+#[derive(Clone, Debug)]
+pub(crate) struct Controller {
+    pub(crate) digits: usize,
+    pub(crate) dcram: [u8; DCRAM_SIZE],
+    pub(crate) adram: [u8; DCRAM_SIZE],
+    pub(crate) cgram: [[u8; 2]; CGRAM_SLOTS],
+    pub(crate) duty: u8,
+    pub(crate) lights: u8,
+}
+
+impl Controller {
+    pub(crate) fn new() -> Self {
+        Self {
+            digits: DCRAM_SIZE,
+            dcram: [FontTable::CharSpace as u8; DCRAM_SIZE],
+            adram: [0; DCRAM_SIZE],
+            cgram: [[0; 2]; CGRAM_SLOTS],
+            duty: 0,
+            lights: Lights::Normal as u8,
+        }
+    }
+
+    /// Execute one frame, the addresses wrap around like in the controller
+    pub(crate) fn write(&mut self, frame: &[u8]) {
+        let Some((&command, data)) = frame.split_first() else {
+            return;
+        };
+        let arg = (command & 0x0F) as usize;
+        match command & 0xF0 {
+            c if c == Command::DCRamWrite as u8 => {
+                for (i, code) in data.iter().enumerate() {
+                    self.dcram[(arg + i) % DCRAM_SIZE] = *code;
+                }
+            }
+            c if c == Command::CGRamWrite as u8 => {
+                for (i, pattern) in data.chunks_exact(2).enumerate() {
+                    self.cgram[(arg + i) % CGRAM_SLOTS] = [pattern[0], pattern[1]];
+                }
+            }
+            c if c == Command::ADRamWrite as u8 => {
+                for (i, bits) in data.iter().enumerate() {
+                    self.adram[(arg + i) % DCRAM_SIZE] = bits & 0b11;
+                }
+            }
+            c if c == Command::DisplayDutySet as u8 => self.duty = arg as u8,
+            // 16 digits are encoded as 0
+            c if c == Command::NumDigitsSet as u8 => {
+                self.digits = if arg == 0 { DCRAM_SIZE } else { arg }
+            }
+            c if c == Command::Lights as u8 => self.lights = arg as u8,
+            _ => {}
+        }
+    }
+
+    /// Lit segments of the digit at `pos`, `0` is the leftmost digit
+    pub(crate) fn segments(&self, pos: usize) -> Segments {
+        match self.lights {
+            l if l == Lights::Off as u8 => Segments::NONE,
+            l if l == Lights::On as u8 => Segments::ALL,
+            _ => {
+                let code = self.dcram[self.digits - 1 - pos];
+                match FontTable::try_from(code) {
+                    Ok(code) if (code as usize) < CGRAM_SLOTS => {
+                        Segments::from_pattern(self.cgram[code as usize])
+                    }
+                    Ok(code) => glyphs::rom(code).unwrap_or_default().segments(),
+                    Err(()) => Segments::NONE,
+                }
+            }
+        }
+    }
+}
+
+/// The simulators ignore the timing
+const NO_TIMING: FrameTiming = FrameTiming {
+    setup: 0,
+    gap: 0,
+    hold: 0,
+};
+
+/// Show a line of text on a simulator like [HCS12SS59T::display_str()](crate::HCS12SS59T::display_str())
+fn write_line(sim: &mut impl Interface, digits: usize, text: &str) -> Result<(), Error> {
+    let mut frame = [FontTable::CharSpace as u8; DCRAM_SIZE + 1];
+    frame[0] = Command::DCRamWrite as u8;
+    for (code, c) in frame[1..=digits].iter_mut().rev().zip(text.chars()) {
+        *code = DefaultMapper.map(c) as u8;
+    }
+    sim.write_frame(&frame[..=digits], NO_TIMING, &mut StdDelay)
+}
+
+/// Set the brightness of a simulator like [HCS12SS59T::brightness()](crate::HCS12SS59T::brightness())
+fn set_brightness(sim: &mut impl Interface, lights: u8, brightness: u8) -> Result<(), Error> {
+    let mut send = |command: Command, arg: u8| {
+        sim.write_frame(&[command as u8 | arg], NO_TIMING, &mut StdDelay)
+    };
+    match brightness {
+        0 => {
+            send(Command::Lights, Lights::Off as u8)?;
+            send(Command::DisplayDutySet, 0)
+        }
+        1..=15 => {
+            send(Command::DisplayDutySet, brightness)?;
+            if lights == Lights::Off as u8 {
+                send(Command::Lights, Lights::Normal as u8)?;
+            }
+            Ok(())
+        }
+        _ => Err(Error::InvalidInput),
+    }
+}
+
+/// Draw the segments of one digit as 5 rows of 5 box-drawing characters
+fn draw_digit(segments: Segments) -> [[char; 5]; 5] {
+    let on = |segment: Segments, c: char| if segments.contains(segment) { c } else { ' ' };
+    let bar = |left: Segments, right: Segments| {
+        [
+            ' ',
+            on(left, '─'),
+            if segments.contains(left) || segments.contains(right) {
+                '─'
+            } else {
+                ' '
+            },
+            on(right, '─'),
+            ' ',
+        ]
+    };
+    [
+        bar(Segments::A1, Segments::A2),
+        [
+            on(Segments::F, '│'),
+            on(Segments::H, '╲'),
+            on(Segments::I, '│'),
+            on(Segments::J, '╱'),
+            on(Segments::B, '│'),
+        ],
+        bar(Segments::G1, Segments::G2),
+        [
+            on(Segments::E, '│'),
+            on(Segments::M, '╱'),
+            on(Segments::L, '│'),
+            on(Segments::K, '╲'),
+            on(Segments::C, '│'),
+        ],
+        bar(Segments::D1, Segments::D2),
+    ]
+}
+
+/// Simulated display drawing to a terminal
+///
+/// Every frame written by the driver updates the simulated controller and redraws the digits in place,
+/// followed by a status line with brightness and lights setting.
+pub struct TerminalVfd<W = io::Stdout> {
+    out: W,
+    controller: Controller,
+    drawn: bool,
+}
+
+impl TerminalVfd {
+    /// Simulated display drawing to stdout
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write> TerminalVfd<W> {
+    /// Simulated display drawing to `out`
+    ///
+    /// Frames are redrawn with ANSI escape sequences moving the cursor up.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            controller: Controller::new(),
+            drawn: false,
+        }
+    }
+
+    /// Returns the output
+    pub fn release(self) -> W {
+        self.out
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        if self.drawn {
+            write!(self.out, "\x1b[6A")?;
+        }
+        self.drawn = true;
+
+        let digits: Vec<_> = (0..self.controller.digits)
+            .map(|pos| draw_digit(self.controller.segments(pos)))
+            .collect();
+        for row in 0..5 {
+            let line: String = digits
+                .iter()
+                .flat_map(|digit| digit[row].iter().chain([&' ']))
+                .collect();
+            writeln!(self.out, "\x1b[2K{line}")?;
+        }
+        let lights = match self.controller.lights {
+            l if l == Lights::Off as u8 => "off",
+            l if l == Lights::On as u8 => "all on",
+            _ => "normal",
+        };
+        writeln!(
+            self.out,
+            "\x1b[2Kduty {}/15, lights {lights}",
+            self.controller.duty
+        )?;
+        self.out.flush()
+    }
+}
+
+impl<W: Write> Interface for TerminalVfd<W> {
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        _timing: FrameTiming,
+        _delay: &mut D,
+    ) -> Result<(), Error> {
+        self.controller.write(frame);
+        // Output errors are reported like failed transfers
+        self.draw().map_err(|_| Error::Spi)
+    }
+}
+
+impl<W: Write> SegmentTextDisplay for TerminalVfd<W> {
+    fn width(&self) -> usize {
+        self.controller.digits
+    }
+
+    fn write_line(&mut self, text: &str) -> Result<(), Error> {
+        write_line(self, self.controller.digits, text)
+    }
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        set_brightness(self, self.controller.lights, brightness)
+    }
+}
+
+/// Simulated display drawing the segments with lines into an embedded-graphics [DrawTarget]
+///
+/// Every digit takes [DIGIT_SIZE](Self::DIGIT_SIZE) pixels plus [SPACING](Self::SPACING) between the digits.
+/// Lit segments are drawn with the `on` color, dark ones with the `off` color, custom characters included.
+#[cfg(feature = "embedded-graphics")]
+pub struct GraphicsVfd<D, C> {
+    target: D,
+    on: C,
+    off: C,
+    controller: Controller,
+}
+
+#[cfg(feature = "embedded-graphics")]
+impl<D, C> GraphicsVfd<D, C>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    /// Size of one digit in pixels
+    pub const DIGIT_SIZE: Size = Size::new(16, 28);
+    /// Horizontal space between two digits in pixels
+    pub const SPACING: u32 = 8;
+    /// Width of the segment lines in pixels
+    const STROKE: u32 = 2;
+
+    /// Simulated display drawing into `target`
+    pub fn new(target: D, on: C, off: C) -> Self {
+        Self {
+            target,
+            on,
+            off,
+            controller: Controller::new(),
+        }
+    }
+
+    /// Size of a target fitting `digits` digits
+    pub const fn size(digits: u32) -> Size {
+        let margin = 2 * Self::STROKE;
+        Size::new(
+            digits * (Self::DIGIT_SIZE.width + Self::SPACING) - Self::SPACING + 2 * margin,
+            Self::DIGIT_SIZE.height + 2 * margin,
+        )
+    }
+
+    /// The draw target, e.g. to update the simulator window
+    pub fn target(&mut self) -> &mut D {
+        &mut self.target
+    }
+
+    /// Returns the draw target
+    pub fn release(self) -> D {
+        self.target
+    }
+
+    fn draw(&mut self) -> Result<(), D::Error> {
+        let Size { width, height } = Self::DIGIT_SIZE;
+        let (w, h) = (width as i32, height as i32);
+        let (x0, xm, x1, y0, ym, y1) = (0, w / 2, w, 0, h / 2, h);
+        let lines = [
+            (Segments::A1, (x0, y0), (xm, y0)),
+            (Segments::A2, (xm, y0), (x1, y0)),
+            (Segments::F, (x0, y0), (x0, ym)),
+            (Segments::H, (x0, y0), (xm, ym)),
+            (Segments::I, (xm, y0), (xm, ym)),
+            (Segments::J, (x1, y0), (xm, ym)),
+            (Segments::B, (x1, y0), (x1, ym)),
+            (Segments::G1, (x0, ym), (xm, ym)),
+            (Segments::G2, (xm, ym), (x1, ym)),
+            (Segments::E, (x0, ym), (x0, y1)),
+            (Segments::M, (xm, ym), (x0, y1)),
+            (Segments::L, (xm, ym), (xm, y1)),
+            (Segments::K, (xm, ym), (x1, y1)),
+            (Segments::C, (x1, ym), (x1, y1)),
+            (Segments::D1, (x0, y1), (xm, y1)),
+            (Segments::D2, (xm, y1), (x1, y1)),
+        ];
+
+        let margin = 2 * Self::STROKE as i32;
+        for pos in 0..self.controller.digits {
+            let segments = self.controller.segments(pos);
+            let origin = Point::new(margin + pos as i32 * (w + Self::SPACING as i32), margin);
+            // Dark segments first, so lit ones are drawn on top where they overlap
+            for lit in [false, true] {
+                let color = if lit { self.on } else { self.off };
+                let style = PrimitiveStyle::with_stroke(color, Self::STROKE);
+                for (segment, start, end) in lines {
+                    if segments.contains(segment) == lit {
+                        Line::new(origin + Point::from(start), origin + Point::from(end))
+                            .into_styled(style)
+                            .draw(&mut self.target)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+impl<D, C> Interface for GraphicsVfd<D, C>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    fn write_frame<De: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        _timing: FrameTiming,
+        _delay: &mut De,
+    ) -> Result<(), Error> {
+        self.controller.write(frame);
+        // Drawing errors are reported like failed transfers
+        self.draw().map_err(|_| Error::Spi)
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+impl<D, C> SegmentTextDisplay for GraphicsVfd<D, C>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    fn width(&self) -> usize {
+        self.controller.digits
+    }
+
+    fn write_line(&mut self, text: &str) -> Result<(), Error> {
+        write_line(self, self.controller.digits, text)
+    }
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        set_brightness(self, self.controller.lights, brightness)
+    }
+}
+
+/// Entry of the [Recorder] log
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Record<const N: usize = NUM_DIGITS> {
+    /// Content of the display after a DCRAM write, leftmost digit first
+    Frame([FontTable; N]),
+    /// Pattern written to a CGRAM slot
+    Glyph {
+        /// CGRAM slot, see [FontTable::Ram0]
+        slot: usize,
+        /// Written pattern
+        pattern: [u8; 2],
+    },
+}
+
+/// Interface recording the shown frames and custom characters
+///
+/// DCRAM writes append the resulting content of all `N` digits, every CGRAM write appends the
+/// written patterns. Other commands (brightness, lights, ADRAM) are not logged.
+///
+/// DCRAM writes to different digits following each other are recorded as one frame, so a
+/// [flush()](crate::framebuffer::BufferedHCS12SS59T::flush()) sending several dirty runs logs only the flushed frame.
+/// Writing a digit of the frame again, any other command or [end_frame()](Self::end_frame()) start a new one.
+pub struct Recorder<const N: usize = NUM_DIGITS> {
+    controller: Controller,
+    log: Vec<Record<N>>,
+    /// DCRAM addresses written into the last frame of the log, `0` once it is complete
+    open: u16,
+}
+
+impl Recorder {
+    /// Recorder for the default 12 digits
+    pub fn new() -> Self {
+        Self::new_with_digits()
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Recorder<N> {
+    /// Recorder for `N` digits
+    pub fn new_with_digits() -> Self {
+        let mut controller = Controller::new();
+        controller.digits = N;
+        Self {
+            controller,
+            log: Vec::new(),
+            open: 0,
+        }
+    }
+
+    /// Complete the current frame, the next DCRAM write starts a new one
+    ///
+    /// Needed to record single-digit updates of the unbuffered driver as separate frames,
+    /// e.g. with `vfd.interface().end_frame()` between two [set_char()](crate::HCS12SS59T::set_char()) calls.
+    pub fn end_frame(&mut self) {
+        self.open = 0;
+    }
+
+    /// All recorded entries in order
+    pub fn log(&self) -> &[Record<N>] {
+        &self.log
+    }
+
+    /// Recorded frames, skipping the CGRAM writes
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = &[FontTable; N]> {
+        self.log.iter().filter_map(|record| match record {
+            Record::Frame(frame) => Some(frame),
+            Record::Glyph { .. } => None,
+        })
+    }
+
+    /// Remove all entries, the display content is kept
+    pub fn clear(&mut self) {
+        self.log.clear();
+        self.open = 0;
+    }
+
+    /// Content of the display, leftmost digit first
+    pub fn current(&self) -> [FontTable; N] {
+        let dcram = &self.controller.dcram;
+        core::array::from_fn(|pos| {
+            FontTable::try_from(dcram[N - 1 - pos]).unwrap_or(FontTable::CharSpace)
+        })
+    }
+}
+
+impl<const N: usize> Interface for Recorder<N> {
+    fn write_frame<D: DelayNs>(
+        &mut self,
+        frame: &[u8],
+        _timing: FrameTiming,
+        _delay: &mut D,
+    ) -> Result<(), Error> {
+        self.controller.write(frame);
+        let Some((&command, data)) = frame.split_first() else {
+            return Ok(());
+        };
+        match command & 0xF0 {
+            c if c == Command::DCRamWrite as u8 => {
+                let first = (command & 0x0F) as usize;
+                let written =
+                    (0..data.len()).fold(0_u16, |acc, i| acc | 1 << ((first + i) % DCRAM_SIZE));
+                let frame = Record::Frame(self.current());
+                match self.log.last_mut() {
+                    Some(last @ Record::Frame(_)) if self.open != 0 && self.open & written == 0 => {
+                        *last = frame;
+                        self.open |= written;
+                    }
+                    _ => {
+                        self.log.push(frame);
+                        self.open = written;
+                    }
+                }
+                return Ok(());
+            }
+            c if c == Command::CGRamWrite as u8 => {
+                let first = (command & 0x0F) as usize;
+                for (i, pattern) in data.chunks_exact(2).enumerate() {
+                    self.log.push(Record::Glyph {
+                        slot: (first + i) % CGRAM_SLOTS,
+                        pattern: [pattern[0], pattern[1]],
+                    });
+                }
+            }
+            _ => {}
+        }
+        self.open = 0;
+        Ok(())
+    }
+}
+
+impl<const N: usize> SegmentTextDisplay for Recorder<N> {
+    fn width(&self) -> usize {
+        N
+    }
+
+    fn write_line(&mut self, text: &str) -> Result<(), Error> {
+        write_line(self, N, text)
+    }
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        set_brightness(self, self.controller.lights, brightness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::animation::{mode::Cycle, ScrollingText};
+    use crate::framebuffer::BufferedHCS12SS59T;
+    use crate::{NoPin, HCS12SS59T};
+
+    /// Recorded frames as text, leftmost digit first
+    fn frames<const N: usize>(recorder: &Recorder<N>) -> Vec<String> {
+        recorder
+            .frames()
+            .map(|frame| frame.iter().map(|c| char::from(*c)).collect())
+            .collect()
+    }
+
+    fn driver() -> HCS12SS59T<Recorder<4>, NoPin, NoPin, StdDelay, 4> {
+        let mut vfd = HCS12SS59T::builder()
+            .digits::<4>()
+            .build_with_interface(Recorder::new_with_digits(), None::<NoPin>, StdDelay)
+            .init()
+            .unwrap();
+        vfd.interface().clear();
+        vfd
+    }
+
+    #[test]
+    fn scrolling_text() {
+        let mut vfd = driver();
+        let mut text = ScrollingText::<Cycle, 4>::new_with_digits("HELLO", false, Cycle);
+        vfd.run_animation(&mut text, Some(0), 6).unwrap();
+
+        assert_eq!(
+            frames(vfd.interface()),
+            ["HELL", "ELLO", "LLOH", "LOHE", "OHEL", "HELL"]
+        );
+    }
+
+    #[test]
+    fn buffered_flush_is_one_frame() {
+        let mut vfd = BufferedHCS12SS59T::new(driver());
+        vfd.set_char(0, 'A').unwrap();
+        vfd.set_char(2, 'C').unwrap();
+        vfd.set_char(3, 'D').unwrap();
+        vfd.flush().unwrap();
+        vfd.display_str("WXYZ");
+        vfd.flush().unwrap();
+
+        assert_eq!(frames(&vfd.release().destroy().0), ["DC A", "WXYZ"]);
+    }
+
+    #[test]
+    fn rewritten_digits_start_a_new_frame() {
+        let mut vfd = driver();
+        vfd.display_str("AB").unwrap();
+        vfd.display_str("CD").unwrap();
+        vfd.set_char(3, 'E').unwrap();
+        vfd.interface().end_frame();
+        vfd.set_char(2, 'F').unwrap();
+
+        assert_eq!(frames(vfd.interface()), ["AB  ", "CD  ", "ED  ", "EF  "]);
+    }
+}
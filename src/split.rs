@@ -0,0 +1,159 @@
+use core::cell::RefCell;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::interface::Interface;
+use super::{AdramBits, Error, FontTable, HCS12SS59T};
+
+/// Mutex sharing the driver between the handles of [SharedHCS12SS59T::split()]
+///
+/// Implement this for the mutex type of your framework (e.g. RTIC or Embassy).
+/// An implementation for [RefCell] is provided for use from a single execution context.
+pub trait DriverMutex {
+    /// The shared driver
+    type Inner;
+
+    /// Create a new mutex containing `inner`
+    fn create(inner: Self::Inner) -> Self;
+
+    /// Lock the mutex and call `f` with the driver
+    fn lock<R>(&self, f: impl FnOnce(&mut Self::Inner) -> R) -> R;
+}
+
+impl<T> DriverMutex for RefCell<T> {
+    type Inner = T;
+
+    fn create(inner: T) -> Self {
+        RefCell::new(inner)
+    }
+
+    fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.borrow_mut())
+    }
+}
+
+/// Driver inside of a [DriverMutex]
+///
+/// Created by [HCS12SS59T::into_shared()].
+pub struct SharedHCS12SS59T<M> {
+    mutex: M,
+}
+
+impl<M: DriverMutex> SharedHCS12SS59T<M> {
+    /// Split into a handle for power control and a handle for writing text
+    ///
+    /// Both handles can be used from different tasks, access to the bus is serialized by the mutex.
+    pub fn split(&self) -> (PowerControl<'_, M>, TextWriter<'_, M>) {
+        (
+            PowerControl { mutex: &self.mutex },
+            TextWriter { mutex: &self.mutex },
+        )
+    }
+
+    /// Lock the mutex and call `f` with the driver
+    pub fn lock<R>(&self, f: impl FnOnce(&mut M::Inner) -> R) -> R {
+        self.mutex.lock(f)
+    }
+}
+
+impl<IF, RstPin, VdonPin, Delay, const N: usize> HCS12SS59T<IF, RstPin, VdonPin, Delay, N>
+where
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// Move the driver into a mutex of type `M`, so it can be [split](SharedHCS12SS59T::split())
+    pub fn into_shared<M: DriverMutex<Inner = Self>>(self) -> SharedHCS12SS59T<M> {
+        SharedHCS12SS59T {
+            mutex: M::create(self),
+        }
+    }
+}
+
+/// Handle controlling brightness and supply of a shared driver
+pub struct PowerControl<'a, M> {
+    mutex: &'a M,
+}
+
+impl<M, IF, RstPin, VdonPin, Delay, const N: usize> PowerControl<'_, M>
+where
+    M: DriverMutex<Inner = HCS12SS59T<IF, RstPin, VdonPin, Delay, N>>,
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// See [HCS12SS59T::brightness()]
+    pub fn brightness(&mut self, brightness: u8) -> Result<(), Error> {
+        self.mutex.lock(|d| d.brightness(brightness))
+    }
+
+    /// See [HCS12SS59T::vd_on()]
+    pub fn vd_on(&mut self) -> Result<(), Error> {
+        self.mutex.lock(|d| d.vd_on())
+    }
+
+    /// See [HCS12SS59T::vd_off()]
+    pub fn vd_off(&mut self) -> Result<(), Error> {
+        self.mutex.lock(|d| d.vd_off())
+    }
+
+    /// See [HCS12SS59T::standby()]
+    pub fn standby(&mut self, cut_supply: bool) -> Result<(), Error> {
+        self.mutex.lock(|d| d.standby(cut_supply))
+    }
+
+    /// See [HCS12SS59T::wake()]
+    pub fn wake(&mut self) -> Result<(), Error> {
+        self.mutex.lock(|d| d.wake())
+    }
+}
+
+/// Handle writing text and custom characters to a shared driver
+pub struct TextWriter<'a, M> {
+    mutex: &'a M,
+}
+
+impl<M, IF, RstPin, VdonPin, Delay, const N: usize> TextWriter<'_, M>
+where
+    M: DriverMutex<Inner = HCS12SS59T<IF, RstPin, VdonPin, Delay, N>>,
+    IF: Interface,
+    RstPin: OutputPin,
+    VdonPin: OutputPin,
+    Delay: DelayNs,
+{
+    /// See [HCS12SS59T::display()]
+    pub fn display<T>(&mut self, text: T) -> Result<(), Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<FontTable>,
+    {
+        self.mutex.lock(|d| d.display(text))
+    }
+
+    /// See [HCS12SS59T::display_str()]
+    pub fn display_str(&mut self, text: &str) -> Result<(), Error> {
+        self.mutex.lock(|d| d.display_str(text))
+    }
+
+    /// See [HCS12SS59T::set_char()]
+    pub fn set_char<C: Into<FontTable>>(&mut self, addr: u8, char: C) -> Result<(), Error> {
+        self.mutex.lock(|d| d.set_char(addr, char))
+    }
+
+    /// See [HCS12SS59T::set_adram()]
+    pub fn set_adram<A: Into<AdramBits>>(&mut self, addr: u8, value: A) -> Result<(), Error> {
+        self.mutex.lock(|d| d.set_adram(addr, value))
+    }
+
+    /// See [HCS12SS59T::set_cgram_pattern()]
+    pub fn set_cgram_pattern<P: Into<[u8; 2]>>(
+        &mut self,
+        addr: FontTable,
+        pattern: P,
+    ) -> Result<(), Error> {
+        self.mutex.lock(|d| d.set_cgram_pattern(addr, pattern))
+    }
+}
@@ -0,0 +1,711 @@
+//! Widgets updated by a periodic `tick()`
+//!
+//! Widgets keep their own state and are advanced with a free running millisecond timestamp,
+//! which is allowed to wrap around. `tick()` returns wether the content changed and has to be redrawn.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use super::format::{self, Formatted, IntFormat};
+use super::framebuffer::BufferedHCS12SS59T;
+use super::glyph::Glyph;
+use super::interface::Interface;
+use super::{glyphs, Error, FontTable, SegmentTextDisplay, HCS12SS59T};
+
+/// Stopwatch or countdown timer
+///
+/// Shows the time as `MM:SS.t`, minutes grow beyond two digits if necessary.
+/// The `.` is rendered blank by the font, see [set_decimal_point()](crate::framebuffer::BufferedHCS12SS59T::set_decimal_point()).
+///
+/// ``` text
+/// let mut timer = Timer::countdown(90_000);
+/// timer.start(now_ms());
+/// loop {
+///     if timer.tick(now_ms()) {
+///         vfd.display_aligned(timer.text(now_ms()).as_str(), Alignment::Center, ' ');
+///         vfd.flush()?;
+///     }
+/// }
+/// ```
+pub struct Timer {
+    duration: Option<u32>,
+    elapsed: u32,
+    started: Option<u32>,
+    expired: bool,
+    shown: Option<u32>,
+    on_expired: Option<fn()>,
+}
+
+impl Timer {
+    /// Timer counting up from zero
+    pub fn stopwatch() -> Self {
+        Self::new(None)
+    }
+
+    /// Timer counting down from `duration_ms` to zero
+    pub fn countdown(duration_ms: u32) -> Self {
+        Self::new(Some(duration_ms))
+    }
+
+    fn new(duration: Option<u32>) -> Self {
+        Self {
+            duration,
+            elapsed: 0,
+            started: None,
+            expired: false,
+            shown: None,
+            on_expired: None,
+        }
+    }
+
+    /// Set a function called by [tick()](Self::tick()) when the countdown reaches zero
+    pub fn on_expired(&mut self, hook: fn()) {
+        self.on_expired = Some(hook);
+    }
+
+    /// Start or resume counting
+    pub fn start(&mut self, now_ms: u32) {
+        if self.started.is_none() && !self.expired {
+            self.started = Some(now_ms);
+        }
+    }
+
+    /// Stop counting, [start()](Self::start()) resumes at the same time
+    pub fn pause(&mut self, now_ms: u32) {
+        self.elapsed = self.elapsed(now_ms);
+        self.started = None;
+    }
+
+    /// Stop and go back to zero (or the full duration of a countdown)
+    pub fn reset(&mut self) {
+        self.elapsed = 0;
+        self.started = None;
+        self.expired = false;
+        self.shown = None;
+    }
+
+    /// Returns wether the timer is counting
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Returns wether the countdown reached zero
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Time counted so far in milliseconds, limited to the duration for a countdown
+    pub fn elapsed(&self, now_ms: u32) -> u32 {
+        let running = self.started.map_or(0, |start| now_ms.wrapping_sub(start));
+        let elapsed = self.elapsed.saturating_add(running);
+        self.duration
+            .map_or(elapsed, |duration| elapsed.min(duration))
+    }
+
+    /// Time left in milliseconds, always zero for a stopwatch
+    pub fn remaining(&self, now_ms: u32) -> u32 {
+        self.duration
+            .map_or(0, |duration| duration - self.elapsed(now_ms))
+    }
+
+    /// Advance the timer
+    ///
+    /// Stops a countdown reaching zero and calls the [hook](Self::on_expired()).
+    /// Returns `true` if the [text()](Self::text()) changed since the last tick.
+    pub fn tick(&mut self, now_ms: u32) -> bool {
+        if self.duration.is_some() && !self.expired && self.remaining(now_ms) == 0 {
+            self.pause(now_ms);
+            self.expired = true;
+            if let Some(hook) = self.on_expired {
+                hook();
+            }
+        }
+        let tenths = Some(self.tenths(now_ms));
+        let changed = tenths != self.shown;
+        self.shown = tenths;
+        changed
+    }
+
+    /// The time as `MM:SS.t`
+    pub fn text(&self, now_ms: u32) -> Formatted {
+        let tenths = self.tenths(now_ms);
+        let seconds = tenths / 10;
+        let mut text = format::format_int(false, seconds / 60, IntFormat::zero_padded(2));
+        text.push(':');
+        text.push_str(format::format_int(false, seconds % 60, IntFormat::zero_padded(2)).as_str());
+        text.push('.');
+        text.push(char::from(b'0' + (tenths % 10) as u8));
+        text
+    }
+
+    /// Shown time in tenths of a second, a countdown rounds up to reach zero when it expires
+    fn tenths(&self, now_ms: u32) -> u32 {
+        match self.duration {
+            Some(_) => self.remaining(now_ms).div_ceil(100),
+            None => self.elapsed(now_ms) / 100,
+        }
+    }
+}
+
+/// Busy indicator on a single digit
+///
+/// The spinner is a custom character which is redrawn in its CGRAM slot, the rest of the display keeps its content.
+///
+/// ``` text
+/// let mut spinner = Spinner::new(FontTable::Ram0, 11);
+/// vfd.display_str("WORKING")?;
+/// spinner.start(now_ms());
+/// while working() {
+///     if spinner.tick(now_ms()) {
+///         spinner.draw(&mut vfd)?;
+///     }
+/// }
+/// ```
+pub struct Spinner {
+    slot: FontTable,
+    digit: u8,
+    frames: &'static [Glyph],
+    frame_ms: u32,
+    started: Option<u32>,
+    shown: Option<usize>,
+}
+
+impl Spinner {
+    /// Spinner on `digit` (counted from the left, starting at zero), drawn with the CGRAM `slot`
+    pub fn new(slot: FontTable, digit: u8) -> Self {
+        Self {
+            slot,
+            digit,
+            frames: &glyphs::SPINNER,
+            frame_ms: 100,
+            started: None,
+            shown: None,
+        }
+    }
+
+    /// Cycle through `frames` instead of the [rotating spoke](glyphs::SPINNER)
+    pub fn with_frames(mut self, frames: &'static [Glyph]) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// Set the time each frame is shown in milliseconds (at least one)
+    pub fn with_frame_ms(mut self, frame_ms: u32) -> Self {
+        self.frame_ms = frame_ms.max(1);
+        self
+    }
+
+    /// Start spinning with the first frame
+    pub fn start(&mut self, now_ms: u32) {
+        self.started = Some(now_ms);
+        self.shown = None;
+    }
+
+    /// Stop spinning, the last frame stays on the display
+    pub fn stop(&mut self) {
+        self.started = None;
+    }
+
+    /// Returns wether the spinner is running
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Advance the spinner
+    ///
+    /// Returns `true` if the frame changed and has to be [drawn](Self::draw()).
+    pub fn tick(&mut self, now_ms: u32) -> bool {
+        let Some(started) = self.started else {
+            return false;
+        };
+        if self.frames.is_empty() {
+            return false;
+        }
+        let frame =
+            Some((now_ms.wrapping_sub(started) / self.frame_ms) as usize % self.frames.len());
+        let changed = frame != self.shown;
+        self.shown = frame;
+        changed
+    }
+
+    /// Write the current frame to the CGRAM slot and place the slot on the digit
+    pub fn draw<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &self,
+        vfd: &mut HCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        if self.digit as usize >= N {
+            return Err(Error::InvalidInput);
+        }
+        let glyph = self
+            .frames
+            .get(self.shown.unwrap_or(0))
+            .copied()
+            .unwrap_or(Glyph::EMPTY);
+        vfd.set_cgram_pattern(self.slot, glyph)?;
+        vfd.set_char((N - 1) as u8 - self.digit, self.slot)
+    }
+}
+
+/// Action of a [Screensaver]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Saver {
+    /// Rotate the content by one digit every `interval_ms`
+    Shift {
+        /// Time between two shifts in milliseconds
+        interval_ms: u32,
+    },
+    /// Lower the brightness to `brightness` (`0` turns the display off)
+    Dim {
+        /// Brightness while idle
+        brightness: u8,
+    },
+}
+
+/// Protection against burn-in of static content
+///
+/// After `idle_ms` without [activity()](Self::activity()) the content is shifted around or dimmed,
+/// the next activity restores it with the following tick. Works on the [buffered driver](BufferedHCS12SS59T),
+/// shifting uses its [set_shift()](BufferedHCS12SS59T::set_shift()).
+///
+/// ``` text
+/// let mut saver = Screensaver::new(600_000, Saver::Shift { interval_ms: 60_000 });
+/// loop {
+///     if button_pressed() {
+///         saver.activity(now_ms());
+///     }
+///     vfd.display_str(&clock_text());
+///     saver.tick(&mut vfd, now_ms())?;
+///     vfd.flush()?;
+/// }
+/// ```
+pub struct Screensaver {
+    idle_ms: u32,
+    saver: Saver,
+    last_activity: Option<u32>,
+    active_since: Option<u32>,
+    shift: usize,
+    brightness: u8,
+}
+
+impl Screensaver {
+    /// Start the `saver` after `idle_ms` without activity
+    pub fn new(idle_ms: u32, saver: Saver) -> Self {
+        Self {
+            idle_ms,
+            saver,
+            last_activity: None,
+            active_since: None,
+            shift: 0,
+            brightness: 0,
+        }
+    }
+
+    /// Note user activity or a content change, which stops the saver on the next tick
+    pub fn activity(&mut self, now_ms: u32) {
+        self.last_activity = Some(now_ms);
+    }
+
+    /// Returns wether the saver is active
+    pub fn is_active(&self) -> bool {
+        self.active_since.is_some()
+    }
+
+    /// Start, advance or stop the saver
+    ///
+    /// The first tick counts as activity. Shifts only change the buffer settings, they are sent with the next flush.
+    pub fn tick<IF, RstPin, VdonPin, Delay, const N: usize>(
+        &mut self,
+        vfd: &mut BufferedHCS12SS59T<IF, RstPin, VdonPin, Delay, N>,
+        now_ms: u32,
+    ) -> Result<(), Error>
+    where
+        IF: Interface,
+        RstPin: OutputPin,
+        VdonPin: OutputPin,
+        Delay: DelayNs,
+    {
+        let last_activity = *self.last_activity.get_or_insert(now_ms);
+        let idle = now_ms.wrapping_sub(last_activity) >= self.idle_ms;
+        match (idle, self.active_since) {
+            (true, None) => {
+                self.active_since = Some(now_ms);
+                if let Saver::Dim { brightness } = self.saver {
+                    self.brightness = vfd.driver().brightness;
+                    vfd.driver().brightness(brightness)?;
+                }
+            }
+            (true, Some(since)) => {
+                if let Saver::Shift { interval_ms } = self.saver {
+                    if now_ms.wrapping_sub(since) >= interval_ms.max(1) {
+                        self.active_since = Some(since.wrapping_add(interval_ms.max(1)));
+                        self.shift = (self.shift + 1) % N.max(1);
+                        vfd.set_shift(self.shift);
+                    }
+                }
+            }
+            (false, Some(_)) => {
+                self.active_since = None;
+                match self.saver {
+                    Saver::Shift { .. } => {
+                        self.shift = 0;
+                        vfd.set_shift(0);
+                    }
+                    Saver::Dim { .. } => vfd.driver().brightness(self.brightness)?,
+                }
+            }
+            (false, None) => {}
+        }
+        Ok(())
+    }
+}
+
+/// Source of the time of day for [AutoDim], e.g. an RTC
+///
+/// Implemented for closures returning `(hour, minute)`.
+pub trait Clock {
+    /// Current time as `(hour, minute)`, hours from `0` to `23`
+    fn hour_minute(&mut self) -> (u8, u8);
+}
+
+impl<F: FnMut() -> (u8, u8)> Clock for F {
+    fn hour_minute(&mut self) -> (u8, u8) {
+        self()
+    }
+}
+
+/// Brightness of a [AutoDim] schedule from a time of day on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduleEntry {
+    /// Hour the entry starts at, `0` to `23`
+    pub hour: u8,
+    /// Minute the entry starts at
+    pub minute: u8,
+    /// Brightness as for [brightness()](HCS12SS59T::brightness())
+    pub brightness: u8,
+}
+
+impl ScheduleEntry {
+    /// Set `brightness` from `hour:minute` on
+    pub const fn new(hour: u8, minute: u8, brightness: u8) -> Self {
+        Self {
+            hour,
+            minute,
+            brightness,
+        }
+    }
+
+    fn minutes(&self) -> u16 {
+        self.hour as u16 * 60 + self.minute as u16
+    }
+}
+
+/// Brightness following a daily schedule
+///
+/// Every entry is valid until the next one starts, the last entry of the day continues after midnight.
+///
+/// ``` text
+/// const SCHEDULE: &[ScheduleEntry] = &[ScheduleEntry::new(7, 0, 12), ScheduleEntry::new(22, 0, 2)];
+/// let mut dim = AutoDim::new(|| rtc.hour_minute(), SCHEDULE);
+/// loop {
+///     dim.tick(&mut vfd)?;
+/// }
+/// ```
+pub struct AutoDim<'a, C> {
+    clock: C,
+    schedule: &'a [ScheduleEntry],
+    applied: Option<u8>,
+}
+
+impl<'a, C: Clock> AutoDim<'a, C> {
+    /// Apply `schedule` using the time from `clock`, the entries can be in any order
+    pub fn new(clock: C, schedule: &'a [ScheduleEntry]) -> Self {
+        Self {
+            clock,
+            schedule,
+            applied: None,
+        }
+    }
+
+    /// Brightness of the schedule at `hour:minute`, `None` for an empty schedule
+    pub fn brightness_at(&self, hour: u8, minute: u8) -> Option<u8> {
+        let now = ScheduleEntry::new(hour, minute, 0).minutes();
+        let started = self.schedule.iter().filter(|e| e.minutes() <= now);
+        started
+            .max_by_key(|e| e.minutes())
+            .or_else(|| self.schedule.iter().max_by_key(|e| e.minutes()))
+            .map(|e| e.brightness)
+    }
+
+    /// Set the brightness of the schedule, if it changed since the last tick
+    ///
+    /// Brightness changes in between (e.g. by the user) are kept until the schedule moves to the next entry.
+    pub fn tick<D: SegmentTextDisplay>(&mut self, vfd: &mut D) -> Result<(), Error> {
+        let (hour, minute) = self.clock.hour_minute();
+        let brightness = self.brightness_at(hour, minute);
+        if let Some(brightness) = brightness.filter(|b| Some(*b) != self.applied) {
+            vfd.set_brightness(brightness)?;
+            self.applied = Some(brightness);
+        }
+        Ok(())
+    }
+}
+
+/// Brightness following the ambient light, e.g. measured with an LDR
+///
+/// `sensor` is polled on every tick and returns a raw reading, which is mapped linearly from the `dark` reading
+/// to the lowest and from the `bright` reading to the highest brightness. Readings can grow or fall with the light.
+/// The brightness only changes when the reading moved by more than the hysteresis since the last change.
+///
+/// ``` text
+/// let mut ambient = AmbientDim::new(|| adc.read(&mut ldr_pin).unwrap(), 200, 3500).with_hysteresis(100);
+/// loop {
+///     ambient.tick(&mut vfd)?;
+/// }
+/// ```
+pub struct AmbientDim<S> {
+    sensor: S,
+    dark: u16,
+    bright: u16,
+    min: u8,
+    max: u8,
+    hysteresis: u16,
+    applied: Option<(u16, u8)>,
+}
+
+impl<S: FnMut() -> u16> AmbientDim<S> {
+    /// Map readings of `sensor` from `dark` to `bright` onto the brightness `1` to `15`
+    pub fn new(sensor: S, dark: u16, bright: u16) -> Self {
+        Self {
+            sensor,
+            dark,
+            bright,
+            min: 1,
+            max: 15,
+            hysteresis: 0,
+            applied: None,
+        }
+    }
+
+    /// Set the brightness range, values are clamped to `1..=15`
+    pub fn with_range(mut self, min: u8, max: u8) -> Self {
+        self.min = min.clamp(1, 15);
+        self.max = max.clamp(self.min, 15);
+        self
+    }
+
+    /// Set the change of the reading needed to change the brightness
+    pub fn with_hysteresis(mut self, hysteresis: u16) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Brightness for a sensor reading
+    pub fn brightness_for(&self, reading: u16) -> u8 {
+        let span = self.dark.abs_diff(self.bright) as u32;
+        let light = reading.clamp(self.dark.min(self.bright), self.dark.max(self.bright));
+        let light = light.abs_diff(self.dark) as u32;
+        let steps = (self.max - self.min) as u32;
+        match span {
+            0 => self.max,
+            _ => self.min + ((light * steps + span / 2) / span) as u8,
+        }
+    }
+
+    /// Poll the sensor and set the brightness if necessary
+    pub fn tick<D: SegmentTextDisplay>(&mut self, vfd: &mut D) -> Result<(), Error> {
+        let reading = (self.sensor)();
+        if let Some((last, _)) = self.applied {
+            if reading.abs_diff(last) <= self.hysteresis {
+                return Ok(());
+            }
+        }
+        let brightness = self.brightness_for(reading);
+        if self.applied.map(|(_, applied)| applied) != Some(brightness) {
+            vfd.set_brightness(brightness)?;
+            self.applied = Some((reading, brightness));
+        }
+        Ok(())
+    }
+}
+
+/// Entry of a [MessageQueue]
+#[cfg(feature = "heapless")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Message<'a> {
+    /// Text to display
+    pub text: &'a str,
+    /// Time the message is shown in milliseconds
+    pub duration_ms: u32,
+    /// Number of times the message is shown, `0` repeats it until the queue is [cleared](MessageQueue::clear())
+    pub repeat: u8,
+    /// Messages with a higher priority preempt the others
+    pub priority: u8,
+}
+
+#[cfg(feature = "heapless")]
+impl<'a> Message<'a> {
+    /// Message shown once for `duration_ms`, with priority `0`
+    pub const fn new(text: &'a str, duration_ms: u32) -> Self {
+        Self {
+            text,
+            duration_ms,
+            repeat: 1,
+            priority: 0,
+        }
+    }
+
+    /// Set the number of times the message is shown, `0` repeats it forever
+    pub const fn with_repeat(mut self, repeat: u8) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Set the priority
+    pub const fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+#[cfg(feature = "heapless")]
+struct QueuedMessage<'a> {
+    message: Message<'a>,
+    id: u32,
+    shown_ms: u32,
+}
+
+/// Queue of up to `Q` messages, played by priority
+///
+/// Messages of the same priority are shown in the order they were pushed. A message with a higher priority
+/// preempts the current one, which resumes with its remaining time afterwards.
+///
+/// ``` text
+/// let mut queue = MessageQueue::<8>::new();
+/// queue.push(Message::new("WELCOME", 3000).with_repeat(0))?;
+/// queue.push(Message::new("DOOR OPEN", 5000).with_priority(9))?;
+/// loop {
+///     if queue.tick(now_ms()) {
+///         vfd.display_str(queue.text())?;
+///     }
+/// }
+/// ```
+#[cfg(feature = "heapless")]
+pub struct MessageQueue<'a, const Q: usize> {
+    messages: heapless::Vec<QueuedMessage<'a>, Q>,
+    next_id: u32,
+    shown: Option<u32>,
+    last_tick: Option<u32>,
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const Q: usize> Default for MessageQueue<'a, Q> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const Q: usize> MessageQueue<'a, Q> {
+    /// Empty queue
+    pub const fn new() -> Self {
+        Self {
+            messages: heapless::Vec::new(),
+            next_id: 0,
+            shown: None,
+            last_tick: None,
+        }
+    }
+
+    /// Add a message, returns it back if the queue is full
+    pub fn push(&mut self, message: Message<'a>) -> Result<(), Message<'a>> {
+        let queued = QueuedMessage {
+            message,
+            id: self.next_id,
+            shown_ms: 0,
+        };
+        self.messages
+            .push(queued)
+            .map_err(|queued| queued.message)?;
+        self.next_id = self.next_id.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Remove all messages
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Number of queued messages, including the current one
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns wether no message is queued
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Text of the current message, empty if the queue is empty
+    pub fn text(&self) -> &'a str {
+        self.current().map_or("", |queued| queued.message.text)
+    }
+
+    fn current(&self) -> Option<&QueuedMessage<'a>> {
+        let shown = self.shown?;
+        self.messages.iter().find(|queued| queued.id == shown)
+    }
+
+    /// Advance the schedule, returns true if another message has to be displayed
+    ///
+    /// Only the time a message was shown counts towards its duration.
+    pub fn tick(&mut self, now_ms: u32) -> bool {
+        let elapsed = self.last_tick.map_or(0, |last| now_ms.wrapping_sub(last));
+        self.last_tick = Some(now_ms);
+
+        let shown = self.shown;
+        if let Some(idx) = self.messages.iter().position(|q| Some(q.id) == shown) {
+            let queued = &mut self.messages[idx];
+            queued.shown_ms = queued.shown_ms.saturating_add(elapsed);
+            if queued.shown_ms >= queued.message.duration_ms {
+                queued.shown_ms = 0;
+                let mut queued = self.messages.remove(idx);
+                let again = match queued.message.repeat {
+                    0 => true,
+                    1 => false,
+                    _ => {
+                        queued.message.repeat -= 1;
+                        true
+                    }
+                };
+                if again {
+                    // Queued again behind the messages of the same priority, the freed slot is reused
+                    let _ = self.messages.push(queued);
+                }
+            }
+        }
+
+        // The first message with the highest priority
+        let next = self
+            .messages
+            .iter()
+            .fold(None::<&QueuedMessage>, |best, queued| match best {
+                Some(best) if best.message.priority >= queued.message.priority => Some(best),
+                _ => Some(queued),
+            })
+            .map(|queued| queued.id);
+        self.shown = next;
+        next != shown
+    }
+}